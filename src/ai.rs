@@ -0,0 +1,141 @@
+/// A computer player, by the name used to choose it on the command line
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Ai {
+    /// Searches sampled positions with alpha-beta search, at a difficulty
+    AlphaBeta(crate::alpha_beta::Difficulty),
+    /// Searches with information-set Monte Carlo tree search
+    Ismcts,
+    /// Does a random move
+    Random,
+}
+
+/// The maximum time an alpha-beta player thinks per move
+const ALPHA_BETA_TIME_LIMIT: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// The number of iterations an ISMCTS player does per move
+const ISMCTS_ITERATIONS: usize = 2000;
+
+/// The maximum time an ISMCTS player thinks per move
+const ISMCTS_TIME_LIMIT: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Create a computer player, where the alpha-beta players evaluate positions with the weights.
+/// With a seed, the player plays the same way each time
+///
+/// ```
+/// use search_and_destroy_chess_2::ai::Ai;
+/// use search_and_destroy_chess_2::ai::create_player;
+/// use search_and_destroy_chess_2::evaluation::Weights;
+///
+/// let player = create_player(Ai::Random, Some(42), &Weights::new());
+/// assert_eq!(player.get_name(), "random");
+/// ```
+pub fn create_player(ai: Ai, seed: Option<u64>, weights: &crate::evaluation::Weights) -> Box<dyn crate::player::Player> {
+    match (ai, seed) {
+        (Ai::AlphaBeta(difficulty), seed) => {
+            let mut player = match seed {
+                Some(seed) => crate::alpha_beta::AlphaBetaPlayer::from_seed(difficulty, seed),
+                None => crate::alpha_beta::AlphaBetaPlayer::new(difficulty),
+            };
+            player.set_time_limit(Some(ALPHA_BETA_TIME_LIMIT));
+            player.set_weights(weights.clone());
+            Box::new(player)
+        },
+        (Ai::Ismcts, seed) => {
+            let mut player = match seed {
+                Some(seed) => crate::ismcts::IsmctsPlayer::from_seed(ISMCTS_ITERATIONS, seed),
+                None => crate::ismcts::IsmctsPlayer::new(ISMCTS_ITERATIONS),
+            };
+            player.set_time_limit(Some(ISMCTS_TIME_LIMIT));
+            Box::new(player)
+        },
+        (Ai::Random, Some(seed)) => Box::new(crate::random_player::RandomPlayer::from_seed(seed)),
+        (Ai::Random, None) => Box::new(crate::random_player::RandomPlayer::new()),
+    }
+}
+
+/// Get all the computer players
+pub fn get_all_ais() -> Vec<Ai> {
+    let mut ais: Vec<Ai> = crate::alpha_beta::get_all_difficulties().into_iter().map(Ai::AlphaBeta).collect();
+    ais.push(Ai::Ismcts);
+    ais.push(Ai::Random);
+    ais
+}
+
+/// Get a computer player from its name, as used on the command line
+///
+/// ```
+/// use search_and_destroy_chess_2::ai::Ai;
+/// use search_and_destroy_chess_2::ai::get_ai_from_str;
+/// use search_and_destroy_chess_2::alpha_beta::Difficulty;
+///
+/// assert_eq!(get_ai_from_str("ismcts"), Some(Ai::Ismcts));
+/// assert_eq!(get_ai_from_str("random"), Some(Ai::Random));
+/// assert_eq!(get_ai_from_str("hard"), Some(Ai::AlphaBeta(Difficulty::Hard)));
+/// assert_eq!(get_ai_from_str("deep-blue"), None);
+/// ```
+pub fn get_ai_from_str(name: &str) -> Option<Ai> {
+    get_all_ais().into_iter().find(|ai| to_str(*ai) == name)
+}
+
+/// Get the text of the menu to choose the player of a color,
+/// given the name of the current player of that color, if it is not a human
+///
+/// ```
+/// use search_and_destroy_chess_2::ai::get_menu_text;
+/// use search_and_destroy_chess_2::color::Color;
+///
+/// let text = get_menu_text(Color::Black, Some("hard"));
+/// assert!(text.contains("0: human\n"));
+/// assert!(text.contains("> 3: hard\n"));
+/// assert!(get_menu_text(Color::White, None).contains("> 0: human\n"));
+/// ```
+pub fn get_menu_text(color: crate::color::Color, player_name: Option<&str>) -> String {
+    let mut text = format!("{} player (press W or B for the color, M to close)\n", crate::color::to_str(color));
+    let mut names = vec!["human".to_string()];
+    names.extend(get_all_ais().into_iter().map(to_str));
+    for (choice, name) in names.iter().enumerate() {
+        let is_current = match player_name {
+            Some(player_name) => player_name == name,
+            None => choice == 0,
+        };
+        let marker = if is_current { "> " } else { "  " };
+        text += &format!("{}{}: {}\n", marker, choice, name);
+    }
+    text
+}
+
+/// Convert an Ai to its name, as used on the command line,
+/// which is the difficulty for alpha-beta search
+///
+/// ```
+/// use search_and_destroy_chess_2::ai::Ai;
+/// use search_and_destroy_chess_2::ai::to_str;
+///
+/// assert_eq!(to_str(Ai::Random), "random");
+/// ```
+pub fn to_str(ai: Ai) -> String {
+    match ai {
+        Ai::AlphaBeta(difficulty) => crate::alpha_beta::to_str(difficulty),
+        Ai::Ismcts => "ismcts".to_string(),
+        Ai::Random => "random".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_round_trip() {
+        for ai in get_all_ais() {
+            assert_eq!(get_ai_from_str(&to_str(ai)), Some(ai));
+            assert_eq!(create_player(ai, None, &crate::evaluation::Weights::new()).get_name(), to_str(ai));
+        }
+    }
+    #[test]
+    fn same_seed_same_game() {
+        let game_1 = crate::player::play_game(&mut *create_player(Ai::Random, Some(1), &crate::evaluation::Weights::new()), &mut *create_player(Ai::Random, Some(2), &crate::evaluation::Weights::new()), 40);
+        let game_2 = crate::player::play_game(&mut *create_player(Ai::Random, Some(1), &crate::evaluation::Weights::new()), &mut *create_player(Ai::Random, Some(2), &crate::evaluation::Weights::new()), 40);
+        assert_eq!(game_1.get_history(), game_2.get_history());
+    }
+}
@@ -0,0 +1,287 @@
+use crate::belief::Belief;
+use crate::chess_move::Move;
+use crate::evaluation::Weights;
+use crate::observation::Observation;
+use crate::piece_type::PieceType;
+use crate::position::Position;
+use crate::rules::Rules;
+use rand::Rng;
+use rand::SeedableRng;
+use rand::seq::SliceRandom;
+
+/// The score of capturing the enemy king, which wins the game
+const WIN_SCORE: i32 = 1_000_000;
+
+/// How strong an alpha-beta player plays
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+/// A computer player that samples positions that are possible given what it believes,
+/// searches each of these with alpha-beta search to a fixed depth,
+/// and does the move that is best in most of them.
+/// To play weaker, it may blunder by doing a random move instead.
+///
+/// A sampled position is always searched to the full depth,
+/// so a time limit is only checked between samples,
+/// and the samples searched by then vote
+pub struct AlphaBetaPlayer {
+    difficulty: Difficulty,
+    depth: usize,
+    samples: usize,
+    time_limit: Option<std::time::Duration>,
+    blunder_rate: f64,
+    weights: Weights,
+    rng: rand::rngs::StdRng,
+    belief: Option<Belief>,
+}
+
+impl AlphaBetaPlayer {
+
+    /// Create a player that plays at a difficulty
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::alpha_beta::AlphaBetaPlayer;
+    /// use search_and_destroy_chess_2::alpha_beta::Difficulty;
+    ///
+    /// let player = AlphaBetaPlayer::new(Difficulty::Hard);
+    /// assert_eq!(player.get_depth(), 3);
+    /// assert_eq!(player.get_blunder_rate(), 0.0);
+    /// assert_eq!(player.get_time_limit(), None);
+    /// ```
+    pub fn new(difficulty: Difficulty) -> AlphaBetaPlayer {
+        let (depth, samples, blunder_rate) = match difficulty {
+            Difficulty::Easy => (1, 4, 0.3),
+            Difficulty::Normal => (2, 8, 0.1),
+            Difficulty::Hard => (3, 16, 0.0),
+        };
+        AlphaBetaPlayer {
+            difficulty,
+            depth,
+            samples,
+            time_limit: None,
+            blunder_rate,
+            weights: Weights::new(),
+            rng: rand::rngs::StdRng::from_entropy(),
+            belief: None,
+        }
+    }
+
+    /// Create a player that plays at a difficulty, where the seed decides
+    /// which positions are sampled and when it blunders, e.g. to repeat a self-play game
+    pub fn from_seed(difficulty: Difficulty, seed: u64) -> AlphaBetaPlayer {
+        let mut player = AlphaBetaPlayer::new(difficulty);
+        player.rng = rand::rngs::StdRng::seed_from_u64(seed);
+        player
+    }
+
+    /// Get the chance to do a random move instead of the best one
+    pub fn get_blunder_rate(&self) -> f64 { self.blunder_rate }
+
+    /// Get the difficulty the player was created with
+    pub fn get_difficulty(&self) -> Difficulty { self.difficulty }
+
+    /// Get the number of plies searched
+    pub fn get_depth(&self) -> usize { self.depth }
+
+    /// Get the number of positions sampled per move
+    pub fn get_samples(&self) -> usize { self.samples }
+
+    /// Get the maximum time to sample and search per move, if any
+    pub fn get_time_limit(&self) -> Option<std::time::Duration> { self.time_limit }
+
+    /// Get the weights of the evaluation
+    pub fn get_weights(&self) -> &Weights { &self.weights }
+
+    /// Set the chance to do a random move instead of the best one
+    pub fn set_blunder_rate(&mut self, blunder_rate: f64) { self.blunder_rate = blunder_rate }
+
+    /// Set the number of plies searched
+    pub fn set_depth(&mut self, depth: usize) { self.depth = depth }
+
+    /// Set the number of positions sampled per move
+    pub fn set_samples(&mut self, samples: usize) { self.samples = samples }
+
+    /// Set the maximum time to sample and search per move, if any.
+    /// At least one position is sampled, however long its search takes
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::alpha_beta::AlphaBetaPlayer;
+    /// use search_and_destroy_chess_2::alpha_beta::Difficulty;
+    /// use std::time::Duration;
+    ///
+    /// let mut player = AlphaBetaPlayer::new(Difficulty::Hard);
+    /// player.set_time_limit(Some(Duration::from_millis(100)));
+    /// assert_eq!(player.get_time_limit(), Some(Duration::from_millis(100)));
+    /// ```
+    pub fn set_time_limit(&mut self, time_limit: Option<std::time::Duration>) { self.time_limit = time_limit }
+
+    /// Set the weights of the evaluation
+    pub fn set_weights(&mut self, weights: Weights) { self.weights = weights }
+
+    /// Get the index of the best move in a sampled position, with its score.
+    /// Moves the player sees, but that a sampled hidden piece blocks, are skipped.
+    /// Returns None if all moves are blocked, so there is nothing to vote for
+    fn search_root(&self, position: &Position, moves: &[Move], rules: &Rules) -> Option<(usize, i32)> {
+        let mut best: Option<(usize, i32)> = None;
+        let mut alpha = -WIN_SCORE - 1;
+        let sampled_moves = position.get_pseudo_legal_moves(rules);
+        for (index, chess_move) in moves.iter().enumerate() {
+            if !sampled_moves.iter().any(|sampled_move| crate::chess_move::is_same_move(sampled_move, chess_move)) {
+                continue;
+            }
+            let mut child = position.clone();
+            let score = match child.apply_move(chess_move) {
+                Some(piece) if piece.get_type() == PieceType::King => WIN_SCORE,
+                _ => -self.search(&child, self.depth.saturating_sub(1), -WIN_SCORE - 1, -alpha, rules, 1),
+            };
+            if best.is_none_or(|(_, best_score)| score > best_score) {
+                best = Some((index, score));
+            }
+            alpha = alpha.max(score);
+        }
+        best
+    }
+
+    /// Search a position with negamax alpha-beta search,
+    /// giving the score for the player to move.
+    /// Capturing a king wins, where winning sooner is better
+    fn search(&self, position: &Position, depth: usize, mut alpha: i32, beta: i32, rules: &Rules, ply: i32) -> i32 {
+        let color = position.get_side_to_move();
+        if depth == 0 {
+            return crate::evaluation::evaluate(position.get_board(), color, &self.weights)
+        }
+        let mut moves = position.get_pseudo_legal_moves(rules);
+        if moves.is_empty() {
+            return crate::evaluation::evaluate(position.get_board(), color, &self.weights)
+        }
+        // Captures first, as these most often cut off the search
+        moves.sort_by_key(|chess_move| chess_move.get_captured().map_or(0, |piece| -self.weights.get_material(piece.get_type())));
+        let mut best = -WIN_SCORE - 1;
+        for chess_move in &moves {
+            let mut child = position.clone();
+            let score = match child.apply_move(chess_move) {
+                Some(piece) if piece.get_type() == PieceType::King => WIN_SCORE - ply,
+                _ => -self.search(&child, depth - 1, -beta, -alpha, rules, ply + 1),
+            };
+            best = best.max(score);
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                break;
+            }
+        }
+        best
+    }
+}
+
+impl crate::player::Player for AlphaBetaPlayer {
+    fn choose_move(&mut self, observation: &Observation) -> Option<Move> {
+        let moves = observation.get_moves();
+        let mut belief = crate::belief::get_updated_belief(self.belief.take(), observation);
+        let chosen = if moves.len() <= 1 {
+            moves.first().cloned()
+        } else if self.rng.gen_bool(self.blunder_rate.clamp(0.0, 1.0)) {
+            moves.choose(&mut self.rng).cloned()
+        } else {
+            // Each sample votes for its best move, where ties go to the highest total score
+            let start = std::time::Instant::now();
+            let rules = observation.get_rules();
+            let mut votes = vec![(0, 0_i64); moves.len()];
+            for sample in 0..self.samples.max(1) {
+                if sample > 0 && self.time_limit.is_some_and(|time_limit| start.elapsed() >= time_limit) {
+                    break;
+                }
+                let position = crate::determinization::sample_position_from_belief(observation, &belief, &mut self.rng);
+                if let Some((index, score)) = self.search_root(&position, moves, &rules) {
+                    votes[index].0 += 1;
+                    votes[index].1 += score as i64;
+                }
+            }
+            // Without any vote, as hidden pieces blocked all moves in all samples, any move will do
+            match (0..moves.len()).filter(|index| votes[*index].0 > 0).max_by_key(|index| votes[*index]) {
+                Some(best) => Some(moves[best].clone()),
+                None => moves.choose(&mut self.rng).cloned(),
+            }
+        };
+        if let Some(chess_move) = &chosen {
+            belief.do_own_move(observation, chess_move);
+        }
+        self.belief = Some(belief);
+        chosen
+    }
+
+    fn get_name(&self) -> String { to_str(self.difficulty) }
+}
+
+/// Get all the difficulties, from easy to hard
+pub fn get_all_difficulties() -> Vec<Difficulty> {
+    vec![Difficulty::Easy, Difficulty::Normal, Difficulty::Hard]
+}
+
+/// Convert a difficulty to its name
+///
+/// ```
+/// use search_and_destroy_chess_2::alpha_beta::Difficulty;
+/// use search_and_destroy_chess_2::alpha_beta::to_str;
+///
+/// assert_eq!(to_str(Difficulty::Normal), "normal");
+/// ```
+pub fn to_str(difficulty: Difficulty) -> String {
+    match difficulty {
+        Difficulty::Easy => "easy".to_string(),
+        Difficulty::Normal => "normal".to_string(),
+        Difficulty::Hard => "hard".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::Player;
+
+    fn choose_move(fen: &str, difficulty: Difficulty) -> String {
+        let mut game = crate::game::Game::new();
+        game.set_position(crate::fen::position_from_fen(fen).unwrap());
+        let mut player = AlphaBetaPlayer::from_seed(difficulty, 1);
+        player.set_blunder_rate(0.0);
+        crate::chess_move::to_str(&player.choose_move(&game.get_observation()).unwrap())
+    }
+
+    #[test]
+    fn captures_a_free_queen() {
+        assert_eq!(choose_move("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1", Difficulty::Normal), "d2d5");
+    }
+    #[test]
+    fn does_not_give_away_its_queen() {
+        // Taking the pawn on d5 loses the queen to the pawn on e6
+        let position = crate::fen::position_from_fen("4k3/8/4p3/3p4/8/8/3Q4/4K3 w - - 0 1").unwrap();
+        let rules = Rules::new();
+        let moves = position.get_pseudo_legal_moves(&rules);
+        let (index, _) = AlphaBetaPlayer::from_seed(Difficulty::Normal, 1).search_root(&position, &moves, &rules).unwrap();
+        assert_ne!(crate::chess_move::to_str(&moves[index]), "d2d5");
+    }
+    #[test]
+    fn does_not_vote_if_all_moves_are_blocked() {
+        // The pawn on b4 blocks the only move given
+        let position = crate::fen::position_from_fen("4k3/8/8/8/1p6/8/1P6/4K3 w - - 0 1").unwrap();
+        let moves = vec![Move::new(crate::square::Square::new("b2"), crate::square::Square::new("b4"))];
+        assert_eq!(AlphaBetaPlayer::from_seed(Difficulty::Normal, 1).search_root(&position, &moves, &Rules::new()), None);
+    }
+    #[test]
+    fn blunders_with_a_blunder_rate_of_one() {
+        // The search always captures the king, a blunder seldom does
+        let mut game = crate::game::Game::new();
+        game.set_position(crate::fen::position_from_fen("4k3/8/8/8/8/8/8/4RK2 w - - 0 1").unwrap());
+        let observation = game.get_observation();
+        let choose_move = |seed: u64, blunder_rate: f64| {
+            let mut player = AlphaBetaPlayer::from_seed(Difficulty::Easy, seed);
+            player.set_blunder_rate(blunder_rate);
+            crate::chess_move::to_str(&player.choose_move(&observation).unwrap())
+        };
+        assert!((0..10).all(|seed| choose_move(seed, 0.0) == "e1e8"));
+        assert!((0..10).any(|seed| choose_move(seed, 1.0) != "e1e8"));
+    }
+}
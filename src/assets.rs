@@ -175,6 +175,12 @@ impl Assets {
     }
 }
 
+impl Default for Assets {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
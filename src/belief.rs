@@ -0,0 +1,578 @@
+use crate::bitboard::Bitboard;
+use crate::chess_move::Move;
+use crate::color::Color;
+use crate::game::Game;
+use crate::observation::Observation;
+use crate::piece::Piece;
+use crate::piece_type::PieceType;
+use crate::position::Position;
+use crate::square::Square;
+
+/// The squares an enemy piece might be on
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PieceBelief {
+    piece: Piece,
+    squares: Bitboard,
+}
+
+impl PieceBelief {
+
+    /// Create the belief that a piece is on one of some squares
+    pub fn new(piece: Piece, squares: Bitboard) -> PieceBelief {
+        PieceBelief {
+            piece,
+            squares,
+        }
+    }
+
+    /// Get the piece
+    pub fn get_piece(&self) -> &Piece { &self.piece }
+
+    /// Get the squares the piece might be on
+    pub fn get_squares(&self) -> Bitboard { self.squares }
+
+    /// Detect if the square of the piece is known
+    pub fn is_known(&self) -> bool { self.squares.count() == 1 }
+}
+
+/// What a player believes about the enemy pieces it cannot see:
+/// for each enemy piece, the squares it might be on.
+///
+/// Each time the enemy moves, every enemy piece might have moved,
+/// so the squares of each piece grow by the squares it could move to.
+/// Each observation then removes the squares the player sees are not occupied by that piece.
+/// The beliefs are kept per piece, so a belief may include squares
+/// that are only possible if another piece had not moved:
+/// the pieces are never believed to be somewhere they cannot be,
+/// yet they might be believed to be somewhere they are not
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Belief {
+    color: Color,
+    pieces: Vec<PieceBelief>,
+    own_pieces: Bitboard,
+    is_enemy_to_move: bool,
+    fullmove_number: u32,
+    before_own_move: Option<Box<Belief>>,
+}
+
+impl Belief {
+
+    /// Create the belief of a player at the start of a game, where all pieces are known
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::belief::Belief;
+    /// use search_and_destroy_chess_2::color::Color;
+    ///
+    /// let belief = Belief::new(Color::White);
+    /// assert_eq!(belief.get_pieces().len(), 16);
+    /// assert!(belief.get_pieces().iter().all(|piece_belief| piece_belief.is_known()));
+    /// ```
+    pub fn new(color: Color) -> Belief {
+        Belief::from_position(&Position::new(), color)
+    }
+
+    /// Create the belief of a player from a position in which all pieces are known,
+    /// such as the position a game starts from
+    pub fn from_position(position: &Position, color: Color) -> Belief {
+        let board = position.get_board();
+        let enemy = crate::color::get_other_color(color);
+        let pieces = board.get_color_bitboard(enemy)
+            .get_squares()
+            .iter()
+            .filter_map(|square| {
+                let piece = crate::board::get_piece_at_square(board, square)?;
+                Some(PieceBelief::new(piece, Bitboard::from_square(square)))
+            })
+            .collect();
+        Belief {
+            color,
+            pieces,
+            own_pieces: board.get_color_bitboard(color),
+            is_enemy_to_move: position.get_side_to_move() != color,
+            fullmove_number: 0,
+            before_own_move: None,
+        }
+    }
+
+    /// Create the belief of a player that knows nothing but what it sees now,
+    /// e.g. when it joins a game that is going on:
+    /// the pieces that are not seen might be on any square that is not seen
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::belief::Belief;
+    /// use search_and_destroy_chess_2::game::Game;
+    ///
+    /// let belief = Belief::from_observation(&Game::new().get_observation());
+    /// assert_eq!(belief.get_pieces().len(), 16);
+    /// assert_eq!(belief.get_possible_bitboard().count(), 32);
+    /// ```
+    pub fn from_observation(observation: &Observation) -> Belief {
+        let color = observation.get_color();
+        let board = observation.get_position().get_board();
+        let mut belief = Belief {
+            color,
+            pieces: Vec::new(),
+            own_pieces: board.get_color_bitboard(color),
+            is_enemy_to_move: false,
+            fullmove_number: 0,
+            before_own_move: None,
+        };
+        let hidden = !observation.get_visible();
+        belief.pieces = crate::determinization::get_hidden_pieces(observation)
+            .into_iter()
+            .map(|piece| {
+                let squares = crate::move_generator::get_squares_it_can_stand_on(&piece, hidden);
+                PieceBelief::new(piece, squares)
+            })
+            .collect();
+        belief.update(observation);
+        belief
+    }
+
+    /// Update the belief after the player has done a move,
+    /// given what the player observed when choosing it.
+    /// The piece captured, if any, is no longer believed to be anywhere.
+    /// The belief from before the move is kept until the next update,
+    /// in case the game rejects the move
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::belief::Belief;
+    /// use search_and_destroy_chess_2::color::Color;
+    /// use search_and_destroy_chess_2::game::Game;
+    /// use search_and_destroy_chess_2::headless::get_move_from_text;
+    ///
+    /// let game = Game::new();
+    /// let mut belief = Belief::new(Color::White);
+    /// let observation = game.get_observation();
+    /// belief.update(&observation);
+    /// let chess_move = get_move_from_text(game.get_position(), &game.get_rules(), "e4").unwrap();
+    /// belief.do_own_move(&observation, &chess_move);
+    /// assert_eq!(belief.get_pieces().len(), 16);
+    /// ```
+    pub fn do_own_move(&mut self, observation: &Observation, chess_move: &Move) {
+        self.before_own_move = Some(Box::new(self.clone()));
+        let fogged_position = observation.get_position();
+        let captured_square = match crate::position::is_en_passant(fogged_position.get_board(), &fogged_position.get_en_passant(), chess_move) {
+            true => Square::from_indices(chess_move.get_to().get_file_index(), chess_move.get_from().get_rank_index()),
+            false => chess_move.get_to(),
+        };
+        if let Some(captured) = chess_move.get_captured() {
+            self.remove_captured(&captured, &captured_square);
+        }
+        let mut position = fogged_position.clone();
+        position.apply_move(chess_move);
+        self.own_pieces = position.get_board().get_color_bitboard(self.color);
+        self.is_enemy_to_move = true;
+    }
+
+    /// Get the color of the player that believes
+    pub fn get_color(&self) -> Color { self.color }
+
+    /// Get the squares each enemy piece might be on
+    pub fn get_pieces(&self) -> &Vec<PieceBelief> { &self.pieces }
+
+    /// Get the squares any enemy piece might be on, e.g. to show them
+    pub fn get_possible_bitboard(&self) -> Bitboard {
+        let mut squares = Bitboard::empty();
+        for piece_belief in &self.pieces {
+            squares |= piece_belief.squares;
+        }
+        squares
+    }
+
+    /// Get the number of enemy pieces that might be on a square,
+    /// e.g. to show how likely it is to meet an enemy there
+    pub fn get_possible_count(&self, square: &Square) -> usize {
+        self.pieces.iter().filter(|piece_belief| piece_belief.squares.contains(square)).count()
+    }
+
+    /// Update the belief with what the player observes when it is its turn.
+    /// If the enemy has moved since the last update, all its pieces might have moved
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::belief::Belief;
+    /// use search_and_destroy_chess_2::color::Color;
+    /// use search_and_destroy_chess_2::save::game_from_save;
+    /// use search_and_destroy_chess_2::square::Square;
+    ///
+    /// let game = game_from_save("e2e4\n").unwrap();
+    /// let mut belief = Belief::new(Color::Black);
+    /// belief.update(&game.get_observation());
+    /// // The knight on g1 might have stayed there, or moved to f3 or h3
+    /// let knight = belief.get_pieces().iter()
+    ///     .find(|piece_belief| piece_belief.get_squares().contains(&Square::new("g1")))
+    ///     .unwrap();
+    /// assert_eq!(knight.get_squares().count(), 3);
+    /// assert!(knight.get_squares().contains(&Square::new("f3")));
+    /// // The pawns block the bishop on f1
+    /// assert_eq!(belief.get_possible_count(&Square::new("c4")), 1);
+    /// ```
+    pub fn update(&mut self, observation: &Observation) {
+        assert_eq!(observation.get_color(), self.color, "A belief is updated by what its own player observes");
+        let own_pieces = observation.get_position().get_board().get_color_bitboard(self.color);
+        if self.is_enemy_to_move {
+            let lost = self.own_pieces & !own_pieces;
+            self.add_enemy_moves(lost);
+            self.is_enemy_to_move = false;
+        }
+        self.own_pieces = own_pieces;
+        self.fullmove_number = observation.get_position().get_fullmove_number();
+        self.before_own_move = None;
+        self.remove_unseen(observation);
+    }
+
+    /// Add the squares each enemy piece might have moved to,
+    /// where the own pieces that were lost show where a capture was done.
+    /// As only one piece moves, the enemy pieces known to be on a square block the others
+    fn add_enemy_moves(&mut self, lost: Bitboard) {
+        let enemy = crate::color::get_other_color(self.color);
+        let mut known = Bitboard::empty();
+        for piece_belief in self.pieces.iter().filter(|piece_belief| piece_belief.is_known()) {
+            known |= piece_belief.squares;
+        }
+        let occupied = self.own_pieces | known;
+        for piece_belief in self.pieces.iter_mut() {
+            let mut squares = piece_belief.squares;
+            for square in piece_belief.squares.get_squares() {
+                squares |= get_reachable_squares(&piece_belief.piece, &square, enemy, occupied, lost) & !known;
+            }
+            piece_belief.squares = squares;
+        }
+    }
+
+    /// Remove the piece captured on a square
+    fn remove_captured(&mut self, captured: &Piece, square: &Square) {
+        let index = self.pieces.iter()
+            .position(|piece_belief| piece_belief.piece == *captured && piece_belief.squares.contains(square))
+            .or_else(|| self.pieces.iter().position(|piece_belief| piece_belief.squares.contains(square)))
+            .or_else(|| self.pieces.iter().position(|piece_belief| piece_belief.piece.get_type() == PieceType::Pawn));
+        if let Some(index) = index {
+            self.pieces.remove(index);
+        }
+    }
+
+    /// Remove the squares a piece is seen not to be on,
+    /// and keep the squares pieces are seen to be on for those pieces only
+    fn remove_unseen(&mut self, observation: &Observation) {
+        let enemy = crate::color::get_other_color(self.color);
+        let board = observation.get_position().get_board();
+        let visible = observation.get_visible();
+
+        // Each piece seen is the one believed to be there, else a promoted pawn
+        for square in board.get_color_bitboard(enemy).get_squares() {
+            let piece = crate::board::get_piece_at_square(board, &square).unwrap();
+            let candidates: Vec<usize> = (0..self.pieces.len())
+                .filter(|index| self.pieces[*index].piece == piece && self.pieces[*index].squares.contains(&square))
+                .collect();
+            if candidates.len() > 1 {
+                continue;
+            }
+            let index = candidates.first().copied()
+                .or_else(|| self.find_closest(|belief| belief.piece.get_type() == PieceType::Pawn, &square))
+                .or_else(|| self.find_closest(|belief| belief.piece == piece, &square));
+            match index {
+                Some(index) => self.pieces[index] = PieceBelief::new(piece, Bitboard::from_square(&square)),
+                None => self.pieces.push(PieceBelief::new(piece, Bitboard::from_square(&square))),
+            }
+        }
+
+        // Visible squares only hold the pieces seen there
+        for piece_belief in self.pieces.iter_mut() {
+            let seen_there = board.get_piece_bitboard(enemy, piece_belief.piece.get_type());
+            piece_belief.squares = piece_belief.squares & (!visible | seen_there);
+        }
+
+        // A square a piece is known to be on holds no other piece
+        let mut is_changed = true;
+        while is_changed {
+            is_changed = false;
+            for index in 0..self.pieces.len() {
+                if !self.pieces[index].is_known() {
+                    continue;
+                }
+                let known = self.pieces[index].squares;
+                for (other_index, other) in self.pieces.iter_mut().enumerate() {
+                    if other_index != index && !(other.squares & known).is_empty() && other.squares != known {
+                        other.squares = other.squares & !known;
+                        is_changed = true;
+                    }
+                }
+            }
+        }
+
+        // A piece believed to be nowhere might be anywhere that is not seen
+        let hidden = !visible;
+        for piece_belief in self.pieces.iter_mut() {
+            if piece_belief.squares.is_empty() {
+                piece_belief.squares = crate::move_generator::get_squares_it_can_stand_on(&piece_belief.piece, hidden);
+            }
+        }
+    }
+
+    /// Find the piece, of those that match, that might be closest to a square
+    fn find_closest<F: Fn(&PieceBelief) -> bool>(&self, is_match: F, square: &Square) -> Option<usize> {
+        (0..self.pieces.len())
+            .filter(|index| is_match(&self.pieces[*index]))
+            .min_by_key(|index| {
+                self.pieces[*index].squares.get_squares()
+                    .iter()
+                    .map(|other| get_distance(square, other))
+                    .min()
+                    .unwrap_or(usize::MAX)
+            })
+    }
+}
+
+/// Get the belief of a player in a game, by going through the game
+/// as that player saw it. As this only uses the history,
+/// the belief is right after moves are undone or redone
+///
+/// ```
+/// use search_and_destroy_chess_2::belief::get_belief;
+/// use search_and_destroy_chess_2::color::Color;
+/// use search_and_destroy_chess_2::save::game_from_save;
+/// use search_and_destroy_chess_2::square::Square;
+///
+/// let game = game_from_save("e2e4\nd7d5\ne4d5\n").unwrap();
+/// let belief = get_belief(&game, Color::Black);
+/// // The pawn seen on d5 might be the one from c2, via c4, or the one from e2
+/// assert_eq!(belief.get_possible_count(&Square::new("d5")), 2);
+/// assert_eq!(belief.get_pieces().len(), 16);
+/// assert_eq!(get_belief(&game, Color::White).get_pieces().len(), 15);
+/// ```
+pub fn get_belief(game: &Game, color: Color) -> Belief {
+    let first_position = match game.get_history().first() {
+        Some(entry) => entry.get_position(),
+        None => game.get_position(),
+    };
+    let mut belief = Belief::from_position(first_position, color);
+    update_belief(&mut belief, game, 0);
+    belief
+}
+
+/// Update the belief of a player, as made by get_belief when the history had a number of moves,
+/// with the moves done since then, e.g. to keep a belief up to date while a game goes on.
+/// Moves that are undone make the belief wrong, after which it has to be made again
+///
+/// ```
+/// use search_and_destroy_chess_2::belief::get_belief;
+/// use search_and_destroy_chess_2::belief::update_belief;
+/// use search_and_destroy_chess_2::color::Color;
+/// use search_and_destroy_chess_2::save::game_from_save;
+///
+/// let game = game_from_save("e2e4\nd7d5\n").unwrap();
+/// let mut belief = get_belief(&game, Color::White);
+/// let game = game_from_save("e2e4\nd7d5\ne4d5\n").unwrap();
+/// update_belief(&mut belief, &game, 2);
+/// assert_eq!(belief, get_belief(&game, Color::White));
+/// ```
+pub fn update_belief(belief: &mut Belief, game: &Game, n_moves: usize) {
+    let color = belief.color;
+    let rules = game.get_rules();
+    for entry in game.get_history().iter().skip(n_moves) {
+        if entry.get_position().get_side_to_move() != color {
+            continue;
+        }
+        let observation = Observation::new(entry.get_position(), &rules);
+        belief.update(&observation);
+        belief.do_own_move(&observation, entry.get_move());
+    }
+    if game.get_current_player() == color {
+        belief.update(&game.get_observation());
+    }
+}
+
+/// Update the belief of a player with what it observes now, for a player that only
+/// gets observations. A new belief is started if there is none yet,
+/// or if the game went back because moves were undone or a new game started:
+/// from the starting position if the own pieces have not moved yet,
+/// else from what the player sees.
+/// If the game rejected the move the player did, the belief from before that move is used
+///
+/// ```
+/// use search_and_destroy_chess_2::belief::get_updated_belief;
+/// use search_and_destroy_chess_2::game::Game;
+///
+/// let observation = Game::new().get_observation();
+/// let belief = get_updated_belief(None, &observation);
+/// assert!(belief.get_pieces().iter().all(|piece_belief| piece_belief.is_known()));
+/// ```
+pub fn get_updated_belief(belief: Option<Belief>, observation: &Observation) -> Belief {
+    let color = observation.get_color();
+    let fullmove_number = observation.get_position().get_fullmove_number();
+    match belief {
+        Some(mut belief) if belief.color == color && fullmove_number > belief.fullmove_number => {
+            belief.update(observation);
+            belief
+        },
+        Some(Belief { color: belief_color, fullmove_number: belief_fullmove_number, before_own_move: Some(before), .. })
+            if belief_color == color && fullmove_number == belief_fullmove_number && !observation.get_rejected().is_empty() => *before,
+        _ => {
+            let own_pieces = observation.get_position().get_board().get_color_bitboard(color);
+            match fullmove_number == 1 && own_pieces == crate::board::Board::new().get_color_bitboard(color) {
+                true => {
+                    let mut belief = Belief::new(color);
+                    belief.update(observation);
+                    belief
+                },
+                false => Belief::from_observation(observation),
+            }
+        },
+    }
+}
+
+/// Get the number of king moves between two squares
+fn get_distance(square: &Square, other: &Square) -> usize {
+    let file_distance = (square.get_file_index() as i32 - other.get_file_index() as i32).unsigned_abs();
+    let rank_distance = (square.get_rank_index() as i32 - other.get_rank_index() as i32).unsigned_abs();
+    file_distance.max(rank_distance) as usize
+}
+
+/// Get the squares an enemy piece might move to from a square,
+/// given the squares of the pieces that block it.
+/// A pawn only moves diagonally to capture an own piece that was lost,
+/// or the square behind a pawn lost to an en-passant capture.
+/// A king or rook on its starting square might castle, if nothing is in between
+fn get_reachable_squares(piece: &Piece, square: &Square, enemy: Color, occupied: Bitboard, lost: Bitboard) -> Bitboard {
+    let mut squares = match piece.get_type() {
+        PieceType::Bishop => crate::bitboard::get_bishop_attacks(square, occupied),
+        PieceType::King => crate::bitboard::get_king_attacks(square),
+        PieceType::Knight => crate::bitboard::get_knight_attacks(square),
+        PieceType::Queen => crate::bitboard::get_queen_attacks(square, occupied),
+        PieceType::Rook => crate::bitboard::get_rook_attacks(square, occupied),
+        PieceType::Pawn => get_pawn_reachable_squares(square, enemy, occupied, lost),
+    };
+    let back_rank_index = match enemy {
+        Color::White => 0,
+        Color::Black => 7,
+    };
+    if square.get_rank_index() == back_rank_index {
+        // The file moved to, and the files in between the king and the rook
+        let castlings: &[(usize, std::ops::Range<usize>)] = match (piece.get_type(), square.get_file_index()) {
+            (PieceType::King, 4) => &[(2, 1..4), (6, 5..7)],
+            (PieceType::Rook, 0) => &[(3, 1..4)],
+            (PieceType::Rook, 7) => &[(5, 5..7)],
+            _ => &[],
+        };
+        for (file_index, between) in castlings {
+            if between.clone().all(|between_index| !occupied.contains(&Square::from_indices(between_index, back_rank_index))) {
+                squares.add(&Square::from_indices(*file_index, back_rank_index));
+            }
+        }
+    }
+    squares
+}
+
+/// Get the squares an enemy pawn might move to from a square
+fn get_pawn_reachable_squares(square: &Square, enemy: Color, occupied: Bitboard, lost: Bitboard) -> Bitboard {
+    let (forward, starting_rank_index) = crate::move_generator::get_pawn_direction(enemy);
+    let mut squares = Bitboard::empty();
+    if let Some(one_step) = crate::square::get_square_at_offset(square, 0, forward) {
+        if !occupied.contains(&one_step) {
+            squares.add(&one_step);
+            if square.get_rank_index() == starting_rank_index {
+                if let Some(two_steps) = crate::square::get_square_at_offset(square, 0, 2 * forward) {
+                    if !occupied.contains(&two_steps) {
+                        squares.add(&two_steps);
+                    }
+                }
+            }
+        }
+    }
+    for target in crate::bitboard::get_pawn_attacks(square, enemy).get_squares() {
+        let passed = crate::square::get_square_at_offset(&target, 0, -forward);
+        if lost.contains(&target) || passed.is_some_and(|passed| lost.contains(&passed)) {
+            squares.add(&target);
+        }
+    }
+    squares
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Check that, after each move of a game, every enemy piece is believed
+    /// to possibly be on the square it is on
+    fn check_belief_holds_the_truth(moves: &str) {
+        let mut game = Game::new();
+        for text in moves.split_whitespace() {
+            let chess_move = crate::headless::get_move_from_text(game.get_position(), &game.get_rules(), text).unwrap();
+            game.do_move(&chess_move);
+            for color in [Color::White, Color::Black] {
+                let belief = get_belief(&game, color);
+                let board = game.get_position().get_board();
+                let enemy = crate::color::get_other_color(color);
+                for square in board.get_color_bitboard(enemy).get_squares() {
+                    assert!(belief.get_possible_count(&square) > 0, "{} after {}: {} is not believed possible", crate::color::to_str(color), text, square);
+                }
+                assert_eq!(belief.get_pieces().len(), board.get_color_bitboard(enemy).count() as usize);
+            }
+        }
+    }
+
+    #[test]
+    fn belief_holds_the_truth_in_an_open_game() {
+        check_belief_holds_the_truth("e4 e5 Nf3 Nc6 Bc4 Nf6 Ng5 d5 exd5 Na5 Bb5+ c6 dxc6 bxc6 Be2 h6 Nf3 e4 Ne5 Qd4 f4 Bc5 O-O");
+    }
+    #[test]
+    fn belief_holds_the_truth_with_en_passant() {
+        check_belief_holds_the_truth("e4 a6 e5 d5 exd6 Qxd6 d4 Qxd4 Qxd4");
+    }
+    #[test]
+    fn belief_holds_the_truth_with_promotion() {
+        check_belief_holds_the_truth("h4 g5 hxg5 h6 gxh6 Nf6 h7 Ng8 hxg8=Q Rxg8");
+    }
+    #[test]
+    fn belief_holds_the_truth_in_random_games() {
+        use rand::SeedableRng;
+        use rand::seq::SliceRandom;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+        for _ in 0..3 {
+            let mut game = Game::new();
+            while !crate::game_status::is_over(game.get_status()) && game.get_history().len() < 60 {
+                let moves = game.get_position().get_pseudo_legal_moves(&game.get_rules());
+                game.do_move(moves.choose(&mut rng).unwrap());
+                let color = game.get_current_player();
+                let belief = get_belief(&game, color);
+                let enemy_pieces = game.get_position().get_board().get_color_bitboard(crate::color::get_other_color(color));
+                for square in enemy_pieces.get_squares() {
+                    assert!(belief.get_possible_count(&square) > 0);
+                }
+            }
+        }
+    }
+    #[test]
+    fn updated_belief_is_the_belief_from_the_history() {
+        let mut game = Game::new();
+        let mut beliefs = [get_belief(&game, Color::White), get_belief(&game, Color::Black)];
+        for text in "e4 d5 exd5 Nf6 Bb5+ c6 dxc6 Qa5 Nc3 bxc6 Ba4 e5".split_whitespace() {
+            let n_moves = game.get_history().len();
+            let chess_move = crate::headless::get_move_from_text(game.get_position(), &game.get_rules(), text).unwrap();
+            game.do_move(&chess_move);
+            for belief in beliefs.iter_mut() {
+                update_belief(belief, &game, n_moves);
+                assert_eq!(*belief, get_belief(&game, belief.get_color()), "after {}", text);
+            }
+        }
+    }
+    #[test]
+    fn seen_pieces_are_known() {
+        let game = crate::save::game_from_save("e2e4\nd7d5\n").unwrap();
+        let belief = get_belief(&game, Color::White);
+        let d5 = Square::new("d5");
+        let pawn = belief.get_pieces().iter().find(|piece_belief| piece_belief.get_squares().contains(&d5)).unwrap();
+        assert!(pawn.is_known());
+        assert_eq!(belief.get_possible_count(&d5), 1);
+        // The pawn on d5 can only have come from d7, so no pawn is left there
+        let d7 = Square::new("d7");
+        assert!(belief.get_pieces().iter()
+            .filter(|piece_belief| piece_belief.get_piece().get_type() == PieceType::Pawn)
+            .all(|piece_belief| !piece_belief.get_squares().contains(&d7)));
+    }
+    #[test]
+    fn captured_pieces_are_removed() {
+        let game = crate::save::game_from_save("e2e4\nd7d5\ne4d5\n").unwrap();
+        assert_eq!(get_belief(&game, Color::White).get_pieces().len(), 15);
+    }
+}
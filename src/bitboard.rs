@@ -0,0 +1,283 @@
+use crate::color::Color;
+use crate::square::Square;
+
+/// The (file, rank) offsets of a knight
+const KNIGHT_OFFSETS: [(i32, i32); 8] = [(1, 2), (2, 1), (2, -1), (1, -2), (-1, -2), (-2, -1), (-2, 1), (-1, 2)];
+
+/// The (file, rank) offsets of a king
+const KING_OFFSETS: [(i32, i32); 8] = [(0, 1), (1, 1), (1, 0), (1, -1), (0, -1), (-1, -1), (-1, 0), (-1, 1)];
+
+/// The (file, rank) directions of a bishop
+const DIAGONAL_DIRECTIONS: [(i32, i32); 4] = [(1, 1), (1, -1), (-1, -1), (-1, 1)];
+
+/// The (file, rank) directions of a rook
+const ORTHOGONAL_DIRECTIONS: [(i32, i32); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+
+/// The squares a knight attacks, for each square index
+const KNIGHT_ATTACKS: [u64; 64] = create_stepping_attacks(&KNIGHT_OFFSETS);
+
+/// The squares a king attacks, for each square index
+const KING_ATTACKS: [u64; 64] = create_stepping_attacks(&KING_OFFSETS);
+
+/// A set of squares, stored as one bit per square,
+/// where bit 0 is 'a1', bit 1 is 'b1' up to bit 63 for 'h8'.
+/// Combining sets of squares is then a single bitwise operation
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub struct Bitboard {
+    bits: u64,
+}
+
+impl Bitboard {
+
+    /// Create a bitboard from its bits
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::bitboard::Bitboard;
+    /// use search_and_destroy_chess_2::square::Square;
+    ///
+    /// let bitboard = Bitboard::new(0b11);
+    /// assert!(bitboard.contains(&Square::new("a1")));
+    /// assert!(bitboard.contains(&Square::new("b1")));
+    /// assert_eq!(bitboard.count(), 2);
+    /// ```
+    pub fn new(bits: u64) -> Bitboard {
+        Bitboard {
+            bits,
+        }
+    }
+
+    /// Create a bitboard with no squares
+    pub fn empty() -> Bitboard { Bitboard::new(0) }
+
+    /// Create a bitboard with only one square
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::bitboard::Bitboard;
+    /// use search_and_destroy_chess_2::square::Square;
+    ///
+    /// let bitboard = Bitboard::from_square(&Square::new("h8"));
+    /// assert_eq!(bitboard.get(), 1 << 63);
+    /// ```
+    pub fn from_square(square: &Square) -> Bitboard { Bitboard::new(1 << square.get_index()) }
+
+    /// Add a square
+    pub fn add(&mut self, square: &Square) { self.bits |= 1 << square.get_index() }
+
+    /// Detect if a square is in the bitboard
+    pub fn contains(&self, square: &Square) -> bool { self.bits & (1 << square.get_index()) != 0 }
+
+    /// Count the squares
+    pub fn count(&self) -> u32 { self.bits.count_ones() }
+
+    /// Get the bits, where bit 0 is 'a1'
+    pub fn get(&self) -> u64 { self.bits }
+
+    /// Get the squares, from 'a1', 'b1' up to 'h8'
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::bitboard::Bitboard;
+    /// use search_and_destroy_chess_2::square::Square;
+    ///
+    /// let mut bitboard = Bitboard::from_square(&Square::new("c3"));
+    /// bitboard.add(&Square::new("a2"));
+    /// assert_eq!(bitboard.get_squares(), vec![Square::new("a2"), Square::new("c3")]);
+    /// ```
+    pub fn get_squares(&self) -> Vec<Square> {
+        let mut squares: Vec<Square> = Vec::with_capacity(self.count() as usize);
+        let mut bits = self.bits;
+        while bits != 0 {
+            squares.push(Square::from_index(bits.trailing_zeros() as u8));
+            bits &= bits - 1;
+        }
+        squares
+    }
+
+    /// Detect if there are no squares
+    pub fn is_empty(&self) -> bool { self.bits == 0 }
+
+    /// Remove a square
+    pub fn remove(&mut self, square: &Square) { self.bits &= !(1 << square.get_index()) }
+}
+
+impl std::ops::BitAnd for Bitboard {
+    type Output = Bitboard;
+    fn bitand(self, other: Bitboard) -> Bitboard { Bitboard::new(self.bits & other.bits) }
+}
+
+impl std::ops::BitOr for Bitboard {
+    type Output = Bitboard;
+    fn bitor(self, other: Bitboard) -> Bitboard { Bitboard::new(self.bits | other.bits) }
+}
+
+impl std::ops::BitOrAssign for Bitboard {
+    fn bitor_assign(&mut self, other: Bitboard) { self.bits |= other.bits }
+}
+
+impl std::ops::Not for Bitboard {
+    type Output = Bitboard;
+    fn not(self) -> Bitboard { Bitboard::new(!self.bits) }
+}
+
+/// Get the squares a bishop attacks, given the squares that are occupied.
+/// Each ray stops at, and includes, the first occupied square
+///
+/// ```
+/// use search_and_destroy_chess_2::bitboard::Bitboard;
+/// use search_and_destroy_chess_2::bitboard::get_bishop_attacks;
+/// use search_and_destroy_chess_2::square::Square;
+///
+/// let occupied = Bitboard::from_square(&Square::new("c3"));
+/// let attacks = get_bishop_attacks(&Square::new("a1"), occupied);
+/// assert_eq!(attacks.get_squares(), vec![Square::new("b2"), Square::new("c3")]);
+/// ```
+pub fn get_bishop_attacks(square: &Square, occupied: Bitboard) -> Bitboard {
+    get_ray_attacks(square, occupied, &DIAGONAL_DIRECTIONS)
+}
+
+/// Get the squares a king attacks
+///
+/// ```
+/// use search_and_destroy_chess_2::bitboard::get_king_attacks;
+/// use search_and_destroy_chess_2::square::Square;
+///
+/// assert_eq!(get_king_attacks(&Square::new("a1")).count(), 3);
+/// assert_eq!(get_king_attacks(&Square::new("e4")).count(), 8);
+/// ```
+pub fn get_king_attacks(square: &Square) -> Bitboard {
+    Bitboard::new(KING_ATTACKS[square.get_index() as usize])
+}
+
+/// Get the squares a knight attacks
+///
+/// ```
+/// use search_and_destroy_chess_2::bitboard::get_knight_attacks;
+/// use search_and_destroy_chess_2::square::Square;
+///
+/// let attacks = get_knight_attacks(&Square::new("g1"));
+/// assert_eq!(attacks.get_squares(), vec![Square::new("e2"), Square::new("f3"), Square::new("h3")]);
+/// ```
+pub fn get_knight_attacks(square: &Square) -> Bitboard {
+    Bitboard::new(KNIGHT_ATTACKS[square.get_index() as usize])
+}
+
+/// Get the squares a pawn of a color attacks, which are the squares diagonally forward
+///
+/// ```
+/// use search_and_destroy_chess_2::bitboard::get_pawn_attacks;
+/// use search_and_destroy_chess_2::color::Color;
+/// use search_and_destroy_chess_2::square::Square;
+///
+/// let attacks = get_pawn_attacks(&Square::new("a7"), Color::Black);
+/// assert_eq!(attacks.get_squares(), vec![Square::new("b6")]);
+/// ```
+pub fn get_pawn_attacks(square: &Square, color: Color) -> Bitboard {
+    let (forward, _) = crate::move_generator::get_pawn_direction(color);
+    let mut attacks = Bitboard::empty();
+    for file_offset in [-1, 1] {
+        if let Some(target) = crate::square::get_square_at_offset(square, file_offset, forward) {
+            attacks.add(&target);
+        }
+    }
+    attacks
+}
+
+/// Get the squares a queen attacks, given the squares that are occupied
+pub fn get_queen_attacks(square: &Square, occupied: Bitboard) -> Bitboard {
+    get_bishop_attacks(square, occupied) | get_rook_attacks(square, occupied)
+}
+
+/// Get the squares a rook attacks, given the squares that are occupied.
+/// Each ray stops at, and includes, the first occupied square
+///
+/// ```
+/// use search_and_destroy_chess_2::bitboard::Bitboard;
+/// use search_and_destroy_chess_2::bitboard::get_rook_attacks;
+/// use search_and_destroy_chess_2::square::Square;
+///
+/// assert_eq!(get_rook_attacks(&Square::new("d4"), Bitboard::empty()).count(), 14);
+/// ```
+pub fn get_rook_attacks(square: &Square, occupied: Bitboard) -> Bitboard {
+    get_ray_attacks(square, occupied, &ORTHOGONAL_DIRECTIONS)
+}
+
+/// Create the table of squares attacked by a stepping piece, for each square index
+const fn create_stepping_attacks(offsets: &[(i32, i32); 8]) -> [u64; 64] {
+    let mut attacks = [0; 64];
+    let mut index = 0;
+    while index < 64 {
+        let file_index = (index % 8) as i32;
+        let rank_index = (index / 8) as i32;
+        let mut offset_index = 0;
+        while offset_index < offsets.len() {
+            let target_file_index = file_index + offsets[offset_index].0;
+            let target_rank_index = rank_index + offsets[offset_index].1;
+            if target_file_index >= 0 && target_file_index < 8 && target_rank_index >= 0 && target_rank_index < 8 {
+                attacks[index] |= 1 << (target_rank_index * 8 + target_file_index);
+            }
+            offset_index += 1;
+        }
+        index += 1;
+    }
+    attacks
+}
+
+/// Get the squares attacked along rays in the given directions,
+/// where each ray stops at, and includes, the first occupied square
+fn get_ray_attacks(square: &Square, occupied: Bitboard, directions: &[(i32, i32)]) -> Bitboard {
+    let mut attacks = Bitboard::empty();
+    for (file_direction, rank_direction) in directions {
+        let mut file_index = square.get_file_index() as i32 + file_direction;
+        let mut rank_index = square.get_rank_index() as i32 + rank_direction;
+        while (0..8).contains(&file_index) && (0..8).contains(&rank_index) {
+            let target = Square::from_indices(file_index as usize, rank_index as usize);
+            attacks.add(&target);
+            if occupied.contains(&target) {
+                break;
+            }
+            file_index += file_direction;
+            rank_index += rank_direction;
+        }
+    }
+    attacks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_and_remove() {
+        let mut bitboard = Bitboard::empty();
+        bitboard.add(&Square::new("e4"));
+        bitboard.add(&Square::new("e4"));
+        assert_eq!(bitboard.count(), 1);
+        bitboard.remove(&Square::new("e4"));
+        assert!(bitboard.is_empty());
+    }
+    #[test]
+    fn bitwise_operations() {
+        let a1 = Bitboard::from_square(&Square::new("a1"));
+        let h8 = Bitboard::from_square(&Square::new("h8"));
+        assert_eq!((a1 | h8).count(), 2);
+        assert!((a1 & h8).is_empty());
+        assert_eq!((!a1).count(), 63);
+    }
+    #[test]
+    fn knight_in_corner() {
+        let attacks = get_knight_attacks(&Square::new("h8"));
+        assert_eq!(attacks.get_squares(), vec![Square::new("g6"), Square::new("f7")]);
+    }
+    #[test]
+    fn queen_blocked() {
+        let mut occupied = Bitboard::empty();
+        occupied.add(&Square::new("d5"));
+        occupied.add(&Square::new("e4"));
+        let attacks = get_queen_attacks(&Square::new("d4"), occupied);
+        assert!(attacks.contains(&Square::new("d5")));
+        assert!(!attacks.contains(&Square::new("d6")));
+        assert!(attacks.contains(&Square::new("e4")));
+        assert!(!attacks.contains(&Square::new("f4")));
+        assert!(attacks.contains(&Square::new("h8")));
+        assert_eq!(attacks.count(), 21);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::bitboard::Bitboard;
 use crate::color::Color;
 use crate::piece::Piece;
 
@@ -8,13 +9,27 @@ use crate::square::get_nth_file;
 use crate::square::get_nth_rank;
 use crate::square::Square;
 
+/// The piece types, in the order of their bitboards in a Board
+const PIECE_TYPES: [PieceType; 6] = [
+    PieceType::Bishop,
+    PieceType::King,
+    PieceType::Knight,
+    PieceType::Pawn,
+    PieceType::Queen,
+    PieceType::Rook,
+];
+
 /// The position of pieces on a board.
 /// Does not include any temporary information, such as
 /// the possibility of en-passant or castling.
+///
+/// The pieces are stored as bitboards: one per color and one per piece type,
+/// so that a piece of a color and type is at the intersection of both
 #[derive(Debug, PartialEq, Eq, Clone)]
 #[allow(dead_code)]
 pub struct Board {
-    ranks: Vec<crate::rank::Rank>,
+    colors: [Bitboard; 2],
+    piece_types: [Bitboard; 6],
 }
 
 impl Board {
@@ -32,10 +47,57 @@ impl Board {
     /// ```
     #[allow(dead_code)]
     pub fn new() -> Board {
+        Board::from_ranks(crate::rank::create_starting_ranks())
+    }
+
+    /// Create a Board from its ranks, starting at the first rank
+    /// 
+    /// ```
+    /// use search_and_destroy_chess_2::board::Board;
+    /// use search_and_destroy_chess_2::rank::create_starting_ranks;
+    /// 
+    /// let board = Board::from_ranks(create_starting_ranks());
+    /// assert_eq!(board, Board::new());
+    /// ```
+    pub fn from_ranks(ranks: Vec<crate::rank::Rank>) -> Board {
+        assert_eq!(ranks.len(), 8);
 
-        Board {
-            ranks: crate::rank::create_starting_ranks(),
+        let mut board = Board {
+            colors: [Bitboard::empty(); 2],
+            piece_types: [Bitboard::empty(); 6],
+        };
+        for (rank_index, rank) in ranks.iter().enumerate() {
+            for file_index in crate::file_index::get_all_file_indices() {
+                let square = Square::from_indices(file_index.get(), rank_index);
+                board.set_piece_at_square(&square, crate::rank::get_piece(rank, &file_index));
+            }
         }
+        board
+    }
+
+    /// Get the squares of all the pieces of a color
+    /// 
+    /// ```
+    /// use search_and_destroy_chess_2::board::Board;
+    /// use search_and_destroy_chess_2::color::Color;
+    /// 
+    /// let board = Board::new();
+    /// assert_eq!(board.get_color_bitboard(Color::White).count(), 16);
+    /// ```
+    pub fn get_color_bitboard(&self, color: Color) -> Bitboard {
+        self.colors[get_color_index(color)]
+    }
+
+    /// Get the squares that have a piece on them
+    /// 
+    /// ```
+    /// use search_and_destroy_chess_2::board::Board;
+    /// 
+    /// let board = Board::new();
+    /// assert_eq!(board.get_occupied().count(), 32);
+    /// ```
+    pub fn get_occupied(&self) -> Bitboard {
+        self.colors[0] | self.colors[1]
     }
 
     /// Get a piece based on its indices
@@ -51,9 +113,34 @@ impl Board {
     /// ```
     #[allow(dead_code)]
     pub fn get_piece_from_indices(&self, file_index: &FileIndex, rank_index: usize) -> Option<Piece> {
-        crate::rank::get_piece(&self.ranks[rank_index], &file_index)
+        let square = Square::from_indices(file_index.get(), rank_index);
+        let color = match (self.colors[0].contains(&square), self.colors[1].contains(&square)) {
+            (true, _) => Color::White,
+            (_, true) => Color::Black,
+            _ => return None,
+        };
+        PIECE_TYPES.iter()
+            .find(|piece_type| self.piece_types[get_piece_type_index(**piece_type)].contains(&square))
+            .map(|piece_type| Piece::new(color, *piece_type))
     }
-    /// Get the ranks of a chessboard
+
+    /// Get the squares of the pieces of a color and type
+    /// 
+    /// ```
+    /// use search_and_destroy_chess_2::board::Board;
+    /// use search_and_destroy_chess_2::color::Color;
+    /// use search_and_destroy_chess_2::piece_type::PieceType;
+    /// use search_and_destroy_chess_2::square::Square;
+    /// 
+    /// let board = Board::new();
+    /// let knights = board.get_piece_bitboard(Color::Black, PieceType::Knight);
+    /// assert_eq!(knights.get_squares(), vec![Square::new("b8"), Square::new("g8")]);
+    /// ```
+    pub fn get_piece_bitboard(&self, color: Color, piece_type: PieceType) -> Bitboard {
+        self.colors[get_color_index(color)] & self.piece_types[get_piece_type_index(piece_type)]
+    }
+
+    /// Get the ranks of a chessboard, starting at the first rank
     /// 
     /// ```
     /// use search_and_destroy_chess_2::board::Board;
@@ -62,28 +149,180 @@ impl Board {
     /// let ranks = board.get_ranks();
     /// assert_eq!(ranks.len(), 8);
     /// ```
-    pub fn get_ranks(&self) -> &Vec<crate::rank::Rank> {
-        &self.ranks
+    pub fn get_ranks(&self) -> Vec<crate::rank::Rank> {
+        (0..8)
+            .map(|rank_index| crate::rank::Rank::new(
+                crate::file_index::get_all_file_indices()
+                    .iter()
+                    .map(|file_index| self.get_piece_from_indices(file_index, rank_index))
+                    .collect()
+            ))
+            .collect()
+    }
+
+    /// Put a piece at a square, or remove it by putting nothing there
+    /// 
+    /// ```
+    /// use search_and_destroy_chess_2::board::Board;
+    /// use search_and_destroy_chess_2::board::get_piece_at_square;
+    /// use search_and_destroy_chess_2::square::Square;
+    /// 
+    /// let mut board = Board::new();
+    /// board.set_piece_at_square(&Square::new("a1"), None);
+    /// assert_eq!(get_piece_at_square(&board, &Square::new("a1")), None);
+    /// ```
+    pub fn set_piece_at_square(&mut self, square: &Square, piece: Option<Piece>) {
+        for bitboard in self.colors.iter_mut().chain(self.piece_types.iter_mut()) {
+            bitboard.remove(square);
+        }
+        if let Some(piece) = piece {
+            self.colors[get_color_index(piece.get_color())].add(square);
+            self.piece_types[get_piece_type_index(piece.get_type())].add(square);
+        }
+    }
+
+    /// Do a move on the board, returning the piece captured, if any.
+    /// If the move has a promotion, the moved piece becomes of that type.
+    /// 
+    /// ```
+    /// use search_and_destroy_chess_2::board::Board;
+    /// use search_and_destroy_chess_2::board::is_pawn;
+    /// use search_and_destroy_chess_2::chess_move::Move;
+    /// use search_and_destroy_chess_2::square::Square;
+    /// 
+    /// let mut board = Board::new();
+    /// let captured = board.apply_move(&Move::new(Square::new("e2"), Square::new("e4")));
+    /// assert_eq!(captured, None);
+    /// assert!(!is_pawn(&board, Square::new("e2")));
+    /// assert!(is_pawn(&board, Square::new("e4")));
+    /// ```
+    pub fn apply_move(&mut self, chess_move: &crate::chess_move::Move) -> Option<Piece> {
+        let from = chess_move.get_from();
+        let to = chess_move.get_to();
+        let piece = match get_piece_at_square(self, &from) {
+            Some(piece) => piece,
+            None => panic!("There is no piece to move at {}", from.get()),
+        };
+        let moved_piece = match chess_move.get_promotion() {
+            Some(piece_type) => Piece::new(piece.get_color(), piece_type),
+            None => piece,
+        };
+        let captured = get_piece_at_square(self, &to);
+        self.set_piece_at_square(&from, None);
+        self.set_piece_at_square(&to, Some(moved_piece));
+        captured
+    }
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Get the index of the bitboard of a color in a Board
+fn get_color_index(color: Color) -> usize {
+    match color {
+        Color::White => 0,
+        Color::Black => 1,
+    }
+}
+
+/// Get the index of the bitboard of a piece type in a Board
+fn get_piece_type_index(piece_type: PieceType) -> usize {
+    match piece_type {
+        PieceType::Bishop => 0,
+        PieceType::King => 1,
+        PieceType::Knight => 2,
+        PieceType::Pawn => 3,
+        PieceType::Queen => 4,
+        PieceType::Rook => 5,
     }
 }
 
+/// Get the squares a player cannot see, 
+/// which are all the squares that are not visible to that player.
+/// 
+/// ```
+/// use search_and_destroy_chess_2::board::Board;
+/// use search_and_destroy_chess_2::board::get_invisible_squares;
+/// use search_and_destroy_chess_2::color::Color;
+/// use search_and_destroy_chess_2::square::Square;
+/// 
+/// let board = Board::new();
+/// let squares = get_invisible_squares(&board, Color::White);
+/// assert_eq!(squares.len(), 32);
+/// assert!(squares.contains(&Square::new("e5")));
+/// assert!(!squares.contains(&Square::new("e4")));
+/// ```
 pub fn get_invisible_squares(board: &Board, color: Color) -> Vec<Square> {
-    let mut invisible_squares: Vec<Square> = vec![];
-    for rank_index in 1..8 {
-        let rank = &board.get_ranks()[rank_index];
-        for file_index in crate::file_index::get_all_file_indices() {
-            if let Some(piece) = crate::rank::get_piece(&rank, &file_index) {
-                if piece.get_color() == color {
-                invisible_squares.push(
-                    crate::square::Square::new(
-                        &crate::square::create_coordinat_from_indices(&file_index, rank_index as u8)
-                    )
-                );
-            }
+    (!get_visible_bitboard(board, color)).get_squares()
+}
+
+/// Get the board as a player sees it,
+/// i.e. without the pieces on the squares that player cannot see
+/// 
+/// ```
+/// use search_and_destroy_chess_2::board::Board;
+/// use search_and_destroy_chess_2::board::get_fogged_board;
+/// use search_and_destroy_chess_2::color::Color;
+/// 
+/// let board = get_fogged_board(&Board::new(), Color::White);
+/// assert_eq!(board.get_color_bitboard(Color::White).count(), 16);
+/// assert!(board.get_color_bitboard(Color::Black).is_empty());
+/// ```
+pub fn get_fogged_board(board: &Board, color: Color) -> Board {
+    let visible = get_visible_bitboard(board, color);
+    Board {
+        colors: board.colors.map(|bitboard| bitboard & visible),
+        piece_types: board.piece_types.map(|bitboard| bitboard & visible),
+    }
+}
+
+/// Get the squares a player can see.
+/// A square is visible if it is occupied by a piece of that player,
+/// or if one of the player's pieces could move to or attack it.
+/// 
+/// ```
+/// use search_and_destroy_chess_2::board::Board;
+/// use search_and_destroy_chess_2::board::get_visible_squares;
+/// use search_and_destroy_chess_2::color::Color;
+/// use search_and_destroy_chess_2::square::Square;
+/// 
+/// let board = Board::new();
+/// let squares = get_visible_squares(&board, Color::Black);
+/// assert_eq!(squares.len(), 32);
+/// assert!(squares.contains(&Square::new("e5")));
+/// assert!(!squares.contains(&Square::new("e4")));
+/// ```
+pub fn get_visible_squares(board: &Board, color: Color) -> Vec<Square> {
+    get_visible_bitboard(board, color).get_squares()
+}
+
+/// Get the squares a player can see, as a bitboard.
+/// Only a pawn can move to a square it does not attack,
+/// so for the other pieces their attacks are enough
+/// 
+/// ```
+/// use search_and_destroy_chess_2::board::Board;
+/// use search_and_destroy_chess_2::board::get_visible_bitboard;
+/// use search_and_destroy_chess_2::color::Color;
+/// 
+/// let board = Board::new();
+/// assert_eq!(get_visible_bitboard(&board, Color::White).count(), 32);
+/// ```
+pub fn get_visible_bitboard(board: &Board, color: Color) -> Bitboard {
+    let own_pieces = board.get_color_bitboard(color);
+    let mut visible = own_pieces;
+    for square in own_pieces.get_squares() {
+        visible |= crate::move_generator::get_attack_bitboard(board, &square);
+    }
+    for square in board.get_piece_bitboard(color, PieceType::Pawn).get_squares() {
+        for destination in crate::move_generator::get_pseudo_legal_destinations(board, &square, color) {
+            visible.add(&destination);
         }
-      }
-  }
-  invisible_squares
+    }
+    visible
 }
 
 pub fn get_piece_at_square(board: &Board, square: &Square) -> Option<crate::piece::Piece> {
@@ -149,7 +388,7 @@ pub fn get_square_color_from_indices(file_index: &crate::file_index::FileIndex,
 /// ```
 #[allow(dead_code)]
 pub fn is_pawn(board: &Board, square: Square) -> bool {
-    let piece_option = board.get_piece_from_indices(&get_nth_file(&square), get_nth_rank(&square) as usize);
+    let piece_option = board.get_piece_from_indices(&get_nth_file(&square), get_nth_rank(&square));
     match piece_option {
         Some(piece) => piece.get_type() == PieceType::Pawn,
         None => false,
@@ -160,11 +399,25 @@ pub fn is_pawn(board: &Board, square: Square) -> bool {
 mod tests {
     use super::*;
 
+    #[test]
+    fn ranks_round_trip() {
+        let board = crate::fen::board_from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R").unwrap();
+        assert_eq!(Board::from_ranks(board.get_ranks()), board);
+        assert_eq!(board.get_ranks()[4].get()[3], Some(crate::piece::create_white_pawn()));
+    }
+    #[test]
+    fn replacing_a_piece_clears_its_bitboards() {
+        let mut board = Board::new();
+        board.set_piece_at_square(&Square::new("d1"), Some(crate::piece::create_black_knight()));
+        assert!(board.get_piece_bitboard(Color::White, PieceType::Queen).is_empty());
+        assert!(!board.get_color_bitboard(Color::White).contains(&Square::new("d1")));
+        assert_eq!(get_piece_at_square(&board, &Square::new("d1")), Some(crate::piece::create_black_knight()));
+    }
     #[test]
     fn create_board() {
         let board = Board::new();
-        assert_eq!(true, is_pawn(&board, Square::new("a2")));
-        assert_eq!(false, is_pawn(&board, Square::new("a1")));
+        assert!(is_pawn(&board, Square::new("a2")));
+        assert!(!is_pawn(&board, Square::new("a1")));
     }
     #[test]
     fn test_get_square_color_from_square() {
@@ -191,6 +444,60 @@ mod tests {
         assert_eq!(board.get_piece_from_indices(&FileIndex::new(1), 1).unwrap().get_type(), PieceType::Pawn);
     }
     #[test]
+    fn apply_capturing_move() {
+        use crate::chess_move::Move;
+        let mut board = Board::new();
+        let captured = board.apply_move(&Move::new(Square::new("b1"), Square::new("b7")));
+        assert_eq!(captured, Some(crate::piece::create_black_pawn()));
+        assert_eq!(get_piece_at_square(&board, &Square::new("b1")), None);
+        assert_eq!(get_piece_at_square(&board, &Square::new("b7")), Some(crate::piece::create_white_knight()));
+    }
+    #[test]
+    fn apply_promoting_move() {
+        use crate::chess_move::Move;
+        let mut board = Board::new();
+        let mut chess_move = Move::new(Square::new("a2"), Square::new("a8"));
+        chess_move.set_promotion(Some(PieceType::Queen));
+        let captured = board.apply_move(&chess_move);
+        assert_eq!(captured, Some(crate::piece::create_black_rook()));
+        assert_eq!(get_piece_at_square(&board, &Square::new("a8")), Some(crate::piece::create_white_queen()));
+    }
+    #[test]
+    #[should_panic(expected = "There is no piece to move at e4")]
+    fn apply_move_from_empty_square() {
+        let mut board = Board::new();
+        board.apply_move(&crate::chess_move::Move::new(Square::new("e4"), Square::new("e5")));
+    }
+    #[test]
+    fn vision_at_start() {
+        let board = Board::new();
+        for square in get_visible_squares(&board, Color::White) {
+            assert!(get_nth_rank(&square) <= 3);
+        }
+        for square in get_invisible_squares(&board, Color::White) {
+            assert!(get_nth_rank(&square) >= 4);
+        }
+    }
+    #[test]
+    fn vision_of_lone_piece() {
+        let mut board = Board::from_ranks(vec![crate::rank::Rank::new(vec![None; 8]); 8]);
+        board.set_piece_at_square(&Square::new("a1"), Some(crate::piece::create_black_knight()));
+        board.set_piece_at_square(&Square::new("h8"), Some(crate::piece::create_white_king()));
+        let mut coordinats: Vec<String> = get_visible_squares(&board, Color::Black).iter().map(|square| square.get()).collect();
+        coordinats.sort();
+        assert_eq!(coordinats, vec!["a1", "b3", "c2"]);
+        assert_eq!(get_invisible_squares(&board, Color::Black).len(), 61);
+    }
+    #[test]
+    fn vision_includes_pawn_attacks_but_not_blocked_squares() {
+        let mut board = Board::from_ranks(vec![crate::rank::Rank::new(vec![None; 8]); 8]);
+        board.set_piece_at_square(&Square::new("d4"), Some(crate::piece::create_white_pawn()));
+        board.set_piece_at_square(&Square::new("d5"), Some(crate::piece::create_black_pawn()));
+        let mut coordinats: Vec<String> = get_visible_squares(&board, Color::White).iter().map(|square| square.get()).collect();
+        coordinats.sort();
+        assert_eq!(coordinats, vec!["c5", "d4", "e5"]);
+    }
+    #[test]
     fn get_piece_at_empty_square() {
         let board = Board::new();
         assert_eq!(get_piece_at_square(&board, &crate::square::Square::new("e4")), None);
@@ -0,0 +1,165 @@
+use crate::color::Color;
+use crate::square::Square;
+
+/// The rights of both players to castle on either side of the board.
+/// A right is lost when the king or that rook moves, or when that rook is captured.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct CastlingRights {
+    white_king_side: bool,
+    white_queen_side: bool,
+    black_king_side: bool,
+    black_queen_side: bool,
+}
+
+impl CastlingRights {
+
+    /// Create the castling rights at the start of a game,
+    /// where both players can castle on both sides
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::castling_rights::CastlingRights;
+    /// use search_and_destroy_chess_2::color::Color;
+    ///
+    /// let castling_rights = CastlingRights::new();
+    /// assert!(castling_rights.can_castle_king_side(Color::White));
+    /// assert!(castling_rights.can_castle_queen_side(Color::Black));
+    /// ```
+    pub fn new() -> CastlingRights {
+        CastlingRights {
+            white_king_side: true,
+            white_queen_side: true,
+            black_king_side: true,
+            black_queen_side: true,
+        }
+    }
+
+    /// Create castling rights where no player can castle anymore
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::castling_rights::CastlingRights;
+    /// use search_and_destroy_chess_2::color::Color;
+    ///
+    /// let castling_rights = CastlingRights::none();
+    /// assert!(!castling_rights.can_castle_king_side(Color::White));
+    /// assert!(!castling_rights.can_castle_queen_side(Color::Black));
+    /// ```
+    pub fn none() -> CastlingRights {
+        CastlingRights {
+            white_king_side: false,
+            white_queen_side: false,
+            black_king_side: false,
+            black_queen_side: false,
+        }
+    }
+
+    /// Can the player castle king-side, i.e. with the rook on the h file?
+    pub fn can_castle_king_side(&self, color: Color) -> bool {
+        match color {
+            Color::White => self.white_king_side,
+            Color::Black => self.black_king_side,
+        }
+    }
+
+    /// Can the player castle queen-side, i.e. with the rook on the a file?
+    pub fn can_castle_queen_side(&self, color: Color) -> bool {
+        match color {
+            Color::White => self.white_queen_side,
+            Color::Black => self.black_queen_side,
+        }
+    }
+
+    /// Set if the player can castle king-side
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::castling_rights::CastlingRights;
+    /// use search_and_destroy_chess_2::color::Color;
+    ///
+    /// let mut castling_rights = CastlingRights::new();
+    /// castling_rights.set_king_side(Color::Black, false);
+    /// assert!(!castling_rights.can_castle_king_side(Color::Black));
+    /// assert!(castling_rights.can_castle_king_side(Color::White));
+    /// ```
+    pub fn set_king_side(&mut self, color: Color, can_castle: bool) {
+        match color {
+            Color::White => self.white_king_side = can_castle,
+            Color::Black => self.black_king_side = can_castle,
+        }
+    }
+
+    /// Set if the player can castle queen-side
+    pub fn set_queen_side(&mut self, color: Color, can_castle: bool) {
+        match color {
+            Color::White => self.white_queen_side = can_castle,
+            Color::Black => self.black_queen_side = can_castle,
+        }
+    }
+
+    /// Remove the castling rights that are lost when a piece moves from or to a square.
+    /// Moving from a king or rook starting square loses the right to castle with it,
+    /// moving to a rook starting square captures that rook.
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::castling_rights::CastlingRights;
+    /// use search_and_destroy_chess_2::color::Color;
+    /// use search_and_destroy_chess_2::square::Square;
+    ///
+    /// let mut castling_rights = CastlingRights::new();
+    /// castling_rights.remove_for_square(&Square::new("e1"));
+    /// assert!(!castling_rights.can_castle_king_side(Color::White));
+    /// assert!(!castling_rights.can_castle_queen_side(Color::White));
+    /// assert!(castling_rights.can_castle_king_side(Color::Black));
+    /// ```
+    pub fn remove_for_square(&mut self, square: &Square) {
+        match square.get().as_str() {
+            "a1" => self.white_queen_side = false,
+            "e1" => {
+                self.white_king_side = false;
+                self.white_queen_side = false;
+            },
+            "h1" => self.white_king_side = false,
+            "a8" => self.black_queen_side = false,
+            "e8" => {
+                self.black_king_side = false;
+                self.black_queen_side = false;
+            },
+            "h8" => self.black_king_side = false,
+            _ => {},
+        }
+    }
+}
+
+impl Default for CastlingRights {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moving_rook_removes_one_side() {
+        let mut castling_rights = CastlingRights::new();
+        castling_rights.remove_for_square(&Square::new("h8"));
+        assert!(!castling_rights.can_castle_king_side(Color::Black));
+        assert!(castling_rights.can_castle_queen_side(Color::Black));
+        castling_rights.remove_for_square(&Square::new("a1"));
+        assert!(castling_rights.can_castle_king_side(Color::White));
+        assert!(!castling_rights.can_castle_queen_side(Color::White));
+    }
+    #[test]
+    fn other_squares_keep_rights() {
+        let mut castling_rights = CastlingRights::new();
+        castling_rights.remove_for_square(&Square::new("e2"));
+        castling_rights.remove_for_square(&Square::new("b8"));
+        assert_eq!(castling_rights, CastlingRights::new());
+    }
+    #[test]
+    fn set_queen_side() {
+        let mut castling_rights = CastlingRights::none();
+        castling_rights.set_queen_side(Color::White, true);
+        assert!(castling_rights.can_castle_queen_side(Color::White));
+        assert!(!castling_rights.can_castle_queen_side(Color::Black));
+    }
+}
@@ -0,0 +1,162 @@
+use crate::board::Board;
+use crate::board::get_piece_at_square;
+use crate::piece::Piece;
+use crate::piece_type::PieceType;
+use crate::square::Square;
+
+/// A chess move, from one square to another.
+///
+/// Named `chess_move`, as `move` is a keyword.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Move {
+    from: Square,
+    to: Square,
+    promotion: Option<PieceType>,
+    captured: Option<Piece>,
+}
+
+impl Move {
+
+    /// Create a new Move, that captures nothing and promotes to nothing
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::chess_move::Move;
+    /// use search_and_destroy_chess_2::square::Square;
+    ///
+    /// let chess_move = Move::new(Square::new("e2"), Square::new("e4"));
+    /// assert_eq!(chess_move.get_from(), Square::new("e2"));
+    /// assert_eq!(chess_move.get_to(), Square::new("e4"));
+    /// assert_eq!(chess_move.get_promotion(), None);
+    /// assert_eq!(chess_move.get_captured(), None);
+    /// ```
+    pub fn new(from: Square, to: Square) -> Move {
+        Move {
+            from,
+            to,
+            promotion: None,
+            captured: None,
+        }
+    }
+
+    /// Get the piece that is captured by this move, if any
+    pub fn get_captured(&self) -> Option<Piece> { self.captured.clone() }
+
+    /// Get the square the piece moves from
+    pub fn get_from(&self) -> Square { self.from }
+
+    /// Get the type of piece a pawn promotes to, if any
+    pub fn get_promotion(&self) -> Option<PieceType> { self.promotion }
+
+    /// Get the square the piece moves to
+    pub fn get_to(&self) -> Square { self.to }
+
+    /// Detect if this move captures a piece
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::chess_move::Move;
+    /// use search_and_destroy_chess_2::piece::create_black_pawn;
+    /// use search_and_destroy_chess_2::square::Square;
+    ///
+    /// let mut chess_move = Move::new(Square::new("e4"), Square::new("d5"));
+    /// assert!(!chess_move.is_capture());
+    /// chess_move.set_captured(Some(create_black_pawn()));
+    /// assert!(chess_move.is_capture());
+    /// ```
+    pub fn is_capture(&self) -> bool { self.captured.is_some() }
+
+    /// Set the piece that is captured by this move
+    pub fn set_captured(&mut self, captured: Option<Piece>) { self.captured = captured }
+
+    /// Set the type of piece a pawn promotes to
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::chess_move::Move;
+    /// use search_and_destroy_chess_2::piece_type::PieceType;
+    /// use search_and_destroy_chess_2::square::Square;
+    ///
+    /// let mut chess_move = Move::new(Square::new("a7"), Square::new("a8"));
+    /// chess_move.set_promotion(Some(PieceType::Queen));
+    /// assert_eq!(chess_move.get_promotion(), Some(PieceType::Queen));
+    /// ```
+    pub fn set_promotion(&mut self, promotion: Option<PieceType>) { self.promotion = promotion }
+}
+
+/// Create a move on a board, with the captured piece (if any)
+/// taken from the board
+///
+/// ```
+/// use search_and_destroy_chess_2::board::Board;
+/// use search_and_destroy_chess_2::chess_move::create_move;
+/// use search_and_destroy_chess_2::square::Square;
+///
+/// let board = Board::new();
+/// let chess_move = create_move(&board, &Square::new("g1"), &Square::new("f3"));
+/// assert!(!chess_move.is_capture());
+/// ```
+pub fn create_move(board: &Board, from: &Square, to: &Square) -> Move {
+    let mut chess_move = Move::new(*from, *to);
+    chess_move.set_captured(get_piece_at_square(board, to));
+    chess_move
+}
+
+/// Detect if two moves move the same piece in the same way,
+/// i.e. if they have the same 'from' and 'to' squares and promote to the same piece type.
+/// The piece captured is not compared, as it may be unknown
+///
+/// ```
+/// use search_and_destroy_chess_2::chess_move::Move;
+/// use search_and_destroy_chess_2::chess_move::is_same_move;
+/// use search_and_destroy_chess_2::piece::create_black_pawn;
+/// use search_and_destroy_chess_2::square::Square;
+///
+/// let chess_move = Move::new(Square::new("e4"), Square::new("d5"));
+/// let mut capture = chess_move.clone();
+/// capture.set_captured(Some(create_black_pawn()));
+/// assert!(is_same_move(&chess_move, &capture));
+/// assert!(!is_same_move(&chess_move, &Move::new(Square::new("e4"), Square::new("e5"))));
+/// ```
+pub fn is_same_move(chess_move: &Move, other: &Move) -> bool {
+    chess_move.get_from() == other.get_from()
+        && chess_move.get_to() == other.get_to()
+        && chess_move.get_promotion() == other.get_promotion()
+}
+
+/// Convert a move to a String in coordinate notation,
+/// i.e. the 'from' and 'to' squares, followed by the lowercase letter
+/// of the piece type a pawn promotes to, if any
+///
+/// ```
+/// use search_and_destroy_chess_2::chess_move::Move;
+/// use search_and_destroy_chess_2::chess_move::to_str;
+/// use search_and_destroy_chess_2::piece_type::PieceType;
+/// use search_and_destroy_chess_2::square::Square;
+///
+/// assert_eq!(to_str(&Move::new(Square::new("e2"), Square::new("e4"))), "e2e4");
+/// let mut chess_move = Move::new(Square::new("a7"), Square::new("a8"));
+/// chess_move.set_promotion(Some(PieceType::Knight));
+/// assert_eq!(to_str(&chess_move), "a7a8n");
+/// ```
+pub fn to_str(chess_move: &Move) -> String {
+    let promotion = match chess_move.get_promotion() {
+        Some(piece_type) => crate::piece_type::to_char(piece_type).to_ascii_lowercase().to_string(),
+        None => String::new(),
+    };
+    format!("{}{}{}", chess_move.get_from(), chess_move.get_to(), promotion)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_quiet_move() {
+        let chess_move = create_move(&Board::new(), &Square::new("e2"), &Square::new("e4"));
+        assert_eq!(chess_move, Move::new(Square::new("e2"), Square::new("e4")));
+    }
+    #[test]
+    fn create_capturing_move() {
+        // Not a legal move, but a capture nonetheless
+        let chess_move = create_move(&Board::new(), &Square::new("d1"), &Square::new("d8"));
+        assert_eq!(chess_move.get_captured(), Some(crate::piece::create_black_queen()));
+    }
+}
@@ -0,0 +1,350 @@
+use crate::ai::Ai;
+use crate::color::Color;
+use crate::convert::Format;
+
+/// How to use the program from the command line
+pub const USAGE: &str = "\
+Usage: search_and_destroy_chess_2 [<command>] [<options>]
+
+Commands:
+  play [--width <pixels>] [--height <pixels>] [--color white|black] [--ai <ai>] [--white <ai>] [--black <ai>] [--weights <file>]
+      Play in a window, which is the default command.
+      With '--color' or '--ai', play that color (default: white)
+      against the computer (default: random), else both colors are played.
+      With '--white' or '--black', the computer plays that color,
+      so it can also play against itself.
+      With '--weights', the alpha-beta AIs use the evaluation weights from that file.
+      In the window, press M to choose the players
+  replay <file> [--ply <ply>] [--headless]
+      Replay a game from a PGN, FEN or saved game, from the given ply.
+      Use Ctrl+Z and Ctrl+Y to step through the moves
+  convert <file> --to fen|pgn|save [--from fen|pgn|save] [--output <file>]
+      Convert a game, where the format of the file is guessed from its extension
+  selfplay [--games <n>] [--white <ai>] [--black <ai>] [--max-plies <n>] [--seed <n>] [--output <file>] [--weights <file>]
+      Let the computer play against itself and show the results,
+      where the alpha-beta AIs use the evaluation weights from the file, if any
+  headless [<file>]
+      Play without a window, reading commands from a file or stdin
+  evaluate [--fen <FEN>] [--color white|black] [--weights <file>]
+      Show how good a position is for a color (default: the one to move),
+      with the weights read from a file of 'key = value' lines
+  perft [--fen <FEN>] [--depth <depth>] [--divide] [--king-capture]
+      Count the positions after all moves, to test the move generator
+  help
+      Show this help
+
+AIs: easy, normal, hard (alpha-beta search at that difficulty), ismcts, random
+Formats: fen, pgn, save";
+
+/// The default width and height of the window, in pixels
+const DEFAULT_WINDOW_SIZE: (u32, u32) = (800, 600);
+
+/// A command given on the command line, with its options
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Command {
+    Convert { input: String, from: Option<Format>, to: Format, output: Option<String> },
+    Evaluate { fen: String, color: Option<Color>, weights: Option<String> },
+    Headless { move_file: Option<String> },
+    Help,
+    Perft { fen: String, depth: usize, divide: bool, king_capture: bool },
+    /// The computer players of White and Black, where None is for a human
+    Play { width: u32, height: u32, white: Option<Ai>, black: Option<Ai>, weights: Option<String> },
+    Replay { filename: String, ply: Option<usize>, headless: bool },
+    Selfplay { games: usize, white: Ai, black: Ai, max_plies: usize, seed: Option<u64>, output: Option<String>, weights: Option<String> },
+}
+
+/// The reasons why the command line cannot be understood
+///
+/// ```
+/// use search_and_destroy_chess_2::cli::CliError;
+///
+/// let error = CliError::InvalidValue { option: "--width".to_string(), value: "wide".to_string() };
+/// assert_eq!(error.to_string(), "Invalid value 'wide' for '--width'");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CliError {
+    InvalidValue { option: String, value: String },
+    MissingArgument { command: String, argument: String },
+    MissingValue(String),
+    UnexpectedArgument { command: String, argument: String },
+    UnknownCommand(String),
+}
+
+impl std::fmt::Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CliError::InvalidValue { option, value } => write!(f, "Invalid value '{}' for '{}'", value, option),
+            CliError::MissingArgument { command, argument } => write!(f, "Command '{}' needs {}", command, argument),
+            CliError::MissingValue(option) => write!(f, "Option '{}' needs a value", option),
+            CliError::UnexpectedArgument { command, argument } => write!(f, "Command '{}' does not take '{}'", command, argument),
+            CliError::UnknownCommand(command) => write!(f, "Unknown command '{}'", command),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/// Read the command from the command line arguments, without the program name.
+/// Without arguments, the command is to play in a window
+///
+/// ```
+/// use search_and_destroy_chess_2::ai::Ai;
+/// use search_and_destroy_chess_2::cli::Command;
+/// use search_and_destroy_chess_2::cli::parse_arguments;
+///
+/// assert_eq!(parse_arguments(&[]), Ok(Command::Play { width: 800, height: 600, white: None, black: None, weights: None }));
+/// let arguments: Vec<String> = ["play", "--color", "black"].iter().map(|s| s.to_string()).collect();
+/// assert_eq!(
+///     parse_arguments(&arguments),
+///     Ok(Command::Play { width: 800, height: 600, white: Some(Ai::Random), black: None, weights: None })
+/// );
+/// ```
+pub fn parse_arguments(arguments: &[String]) -> Result<Command, CliError> {
+    let (command, rest) = match arguments.split_first() {
+        Some((command, rest)) => (command.as_str(), rest),
+        None => ("play", arguments),
+    };
+    let mut options = Options::new(command, rest);
+    let result = match command {
+        "convert" => parse_convert(&mut options)?,
+        "evaluate" => parse_evaluate(&mut options)?,
+        "headless" => Command::Headless { move_file: options.next_argument() },
+        "help" | "--help" | "-h" => Command::Help,
+        "perft" => parse_perft(&mut options)?,
+        "play" => parse_play(&mut options)?,
+        "replay" => parse_replay(&mut options)?,
+        "selfplay" => parse_selfplay(&mut options)?,
+        _ => return Err(CliError::UnknownCommand(command.to_string())),
+    };
+    options.check_all_used()?;
+    Ok(result)
+}
+
+/// The arguments after the command, which are removed once they are read
+struct Options {
+    command: String,
+    arguments: Vec<String>,
+}
+
+impl Options {
+    fn new(command: &str, arguments: &[String]) -> Options {
+        Options {
+            command: command.to_string(),
+            arguments: arguments.to_vec(),
+        }
+    }
+
+    /// Give an error for the first argument that is not read
+    fn check_all_used(&self) -> Result<(), CliError> {
+        match self.arguments.first() {
+            Some(argument) => Err(CliError::UnexpectedArgument { command: self.command.clone(), argument: argument.clone() }),
+            None => Ok(()),
+        }
+    }
+
+    /// Read a flag, i.e. an option without a value
+    fn flag(&mut self, option: &str) -> bool {
+        match self.arguments.iter().position(|argument| argument == option) {
+            Some(index) => {
+                self.arguments.remove(index);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Read the first argument that is not an option, if any
+    fn next_argument(&mut self) -> Option<String> {
+        match self.arguments.first() {
+            Some(argument) if !argument.starts_with("--") => Some(self.arguments.remove(0)),
+            _ => None,
+        }
+    }
+
+    /// Read the value of an option, if the option is given
+    fn value(&mut self, option: &str) -> Result<Option<String>, CliError> {
+        match self.arguments.iter().position(|argument| argument == option) {
+            Some(index) if index + 1 < self.arguments.len() => {
+                let value = self.arguments.remove(index + 1);
+                self.arguments.remove(index);
+                Ok(Some(value))
+            },
+            Some(_) => Err(CliError::MissingValue(option.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    /// Read the value of an option and convert it, if the option is given
+    fn parsed_value<T, F: Fn(&str) -> Option<T>>(&mut self, option: &str, parse: F) -> Result<Option<T>, CliError> {
+        match self.value(option)? {
+            Some(value) => match parse(&value) {
+                Some(parsed) => Ok(Some(parsed)),
+                None => Err(CliError::InvalidValue { option: option.to_string(), value }),
+            },
+            None => Ok(None),
+        }
+    }
+}
+
+/// Get a color from its name on the command line, e.g. 'white'
+fn get_color_from_str(name: &str) -> Option<Color> {
+    match name {
+        "white" => Some(Color::White),
+        "black" => Some(Color::Black),
+        _ => None,
+    }
+}
+
+fn parse_convert(options: &mut Options) -> Result<Command, CliError> {
+    let input = options.next_argument()
+        .ok_or_else(|| CliError::MissingArgument { command: "convert".to_string(), argument: "a file".to_string() })?;
+    let from = options.parsed_value("--from", crate::convert::get_format_from_str)?;
+    let to = options.parsed_value("--to", crate::convert::get_format_from_str)?
+        .ok_or_else(|| CliError::MissingArgument { command: "convert".to_string(), argument: "'--to <format>'".to_string() })?;
+    let output = options.value("--output")?;
+    Ok(Command::Convert { input, from, to, output })
+}
+
+fn parse_evaluate(options: &mut Options) -> Result<Command, CliError> {
+    let fen = options.value("--fen")?.unwrap_or_else(|| crate::fen::STARTING_FEN.to_string());
+    let color = options.parsed_value("--color", get_color_from_str)?;
+    let weights = options.value("--weights")?;
+    Ok(Command::Evaluate { fen, color, weights })
+}
+
+fn parse_perft(options: &mut Options) -> Result<Command, CliError> {
+    let fen = options.value("--fen")?.unwrap_or_else(|| crate::fen::STARTING_FEN.to_string());
+    let depth = options.parsed_value("--depth", |value| value.parse().ok().filter(|depth| *depth >= 1))?.unwrap_or(4);
+    let divide = options.flag("--divide");
+    let king_capture = options.flag("--king-capture");
+    Ok(Command::Perft { fen, depth, divide, king_capture })
+}
+
+fn parse_play(options: &mut Options) -> Result<Command, CliError> {
+    let width = options.parsed_value("--width", |value| value.parse().ok().filter(|width| *width > 0))?
+        .unwrap_or(DEFAULT_WINDOW_SIZE.0);
+    let height = options.parsed_value("--height", |value| value.parse().ok().filter(|height| *height > 0))?
+        .unwrap_or(DEFAULT_WINDOW_SIZE.1);
+    let color = options.parsed_value("--color", get_color_from_str)?;
+    let ai = options.parsed_value("--ai", crate::ai::get_ai_from_str)?;
+    let mut white = options.parsed_value("--white", crate::ai::get_ai_from_str)?;
+    let mut black = options.parsed_value("--black", crate::ai::get_ai_from_str)?;
+    if ai.is_some() || color.is_some() {
+        let opponent = match color.unwrap_or(Color::White) {
+            Color::White => &mut black,
+            Color::Black => &mut white,
+        };
+        opponent.get_or_insert(ai.unwrap_or(Ai::Random));
+    }
+    let weights = options.value("--weights")?;
+    Ok(Command::Play { width, height, white, black, weights })
+}
+
+fn parse_replay(options: &mut Options) -> Result<Command, CliError> {
+    let filename = options.next_argument()
+        .ok_or_else(|| CliError::MissingArgument { command: "replay".to_string(), argument: "a file".to_string() })?;
+    let ply = options.parsed_value("--ply", |value| value.parse().ok())?;
+    let headless = options.flag("--headless");
+    Ok(Command::Replay { filename, ply, headless })
+}
+
+fn parse_selfplay(options: &mut Options) -> Result<Command, CliError> {
+    let games = options.parsed_value("--games", |value| value.parse().ok())?.unwrap_or(1);
+    let white = options.parsed_value("--white", crate::ai::get_ai_from_str)?.unwrap_or(Ai::Random);
+    let black = options.parsed_value("--black", crate::ai::get_ai_from_str)?.unwrap_or(Ai::Random);
+    let max_plies = options.parsed_value("--max-plies", |value| value.parse().ok())?.unwrap_or(500);
+    let seed = options.parsed_value("--seed", |value| value.parse().ok())?;
+    let output = options.value("--output")?;
+    let weights = options.value("--weights")?;
+    Ok(Command::Selfplay { games, white, black, max_plies, seed, output, weights })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(command_line: &str) -> Result<Command, CliError> {
+        let arguments: Vec<String> = command_line.split_whitespace().map(|argument| argument.to_string()).collect();
+        parse_arguments(&arguments)
+    }
+
+    #[test]
+    fn play_with_options() {
+        assert_eq!(
+            parse("play --height 400 --width 500 --ai random"),
+            Ok(Command::Play { width: 500, height: 400, white: None, black: Some(Ai::Random), weights: None })
+        );
+        assert_eq!(parse("play"), Ok(Command::Play { width: 800, height: 600, white: None, black: None, weights: None }));
+        assert_eq!(
+            parse("play --white ismcts --black random"),
+            Ok(Command::Play { width: 800, height: 600, white: Some(Ai::Ismcts), black: Some(Ai::Random), weights: None })
+        );
+        assert_eq!(
+            parse("play --ai hard --weights weights.txt"),
+            Ok(Command::Play {
+                width: 800, height: 600, white: None, black: Some(Ai::AlphaBeta(crate::alpha_beta::Difficulty::Hard)),
+                weights: Some("weights.txt".to_string())
+            })
+        );
+    }
+    #[test]
+    fn evaluate_with_options() {
+        assert_eq!(
+            parse("evaluate --color black --weights weights.txt"),
+            Ok(Command::Evaluate { fen: crate::fen::STARTING_FEN.to_string(), color: Some(Color::Black), weights: Some("weights.txt".to_string()) })
+        );
+    }
+    #[test]
+    fn play_with_invalid_values() {
+        assert_eq!(parse("play --width 0"), Err(CliError::InvalidValue { option: "--width".to_string(), value: "0".to_string() }));
+        assert_eq!(parse("play --color red"), Err(CliError::InvalidValue { option: "--color".to_string(), value: "red".to_string() }));
+        assert_eq!(parse("play --ai"), Err(CliError::MissingValue("--ai".to_string())));
+    }
+    #[test]
+    fn replay() {
+        assert_eq!(parse("replay game.pgn --ply 3"), Ok(Command::Replay { filename: "game.pgn".to_string(), ply: Some(3), headless: false }));
+        assert_eq!(parse("replay --ply 3").unwrap_err().to_string(), "Command 'replay' needs a file");
+    }
+    #[test]
+    fn convert() {
+        assert_eq!(
+            parse("convert game.pgn --to save --output game.txt"),
+            Ok(Command::Convert { input: "game.pgn".to_string(), from: None, to: Format::Save, output: Some("game.txt".to_string()) })
+        );
+        assert_eq!(parse("convert game.pgn").unwrap_err().to_string(), "Command 'convert' needs '--to <format>'");
+    }
+    #[test]
+    fn selfplay() {
+        assert_eq!(
+            parse("selfplay --games 10 --seed 42"),
+            Ok(Command::Selfplay { games: 10, white: Ai::Random, black: Ai::Random, max_plies: 500, seed: Some(42), output: None, weights: None })
+        );
+        assert_eq!(
+            parse("selfplay --white hard --weights weights.txt"),
+            Ok(Command::Selfplay {
+                games: 1, white: Ai::AlphaBeta(crate::alpha_beta::Difficulty::Hard), black: Ai::Random,
+                max_plies: 500, seed: None, output: None, weights: Some("weights.txt".to_string())
+            })
+        );
+    }
+    #[test]
+    fn perft() {
+        assert_eq!(
+            parse("perft --depth 2 --divide"),
+            Ok(Command::Perft { fen: crate::fen::STARTING_FEN.to_string(), depth: 2, divide: true, king_capture: false })
+        );
+    }
+    #[test]
+    fn headless() {
+        assert_eq!(parse("headless"), Ok(Command::Headless { move_file: None }));
+        assert_eq!(parse("headless moves.txt"), Ok(Command::Headless { move_file: Some("moves.txt".to_string()) }));
+    }
+    #[test]
+    fn unknown_and_unexpected() {
+        assert_eq!(parse("dance"), Err(CliError::UnknownCommand("dance".to_string())));
+        assert_eq!(
+            parse("play --fullscreen"),
+            Err(CliError::UnexpectedArgument { command: "play".to_string(), argument: "--fullscreen".to_string() })
+        );
+    }
+}
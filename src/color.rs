@@ -29,6 +29,22 @@ pub fn to_str(color: Color) -> String {
     }
 }
 
+/// Get the other color
+/// 
+/// ```
+/// use search_and_destroy_chess_2::color::Color;
+/// use search_and_destroy_chess_2::color::get_other_color;
+/// 
+/// assert_eq!(get_other_color(Color::Black), Color::White);
+/// assert_eq!(get_other_color(Color::White), Color::Black);
+/// ```
+pub fn get_other_color(color: Color) -> Color {
+    match color {
+        Color::Black => Color::White,
+        Color::White => Color::Black,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -39,6 +55,12 @@ mod tests {
         assert_eq!(str, String::from("Black"));
     }
 
+    #[test]
+    fn other_color() {
+        assert_eq!(get_other_color(Color::White), Color::Black);
+        assert_eq!(get_other_color(get_other_color(Color::White)), Color::White);
+    }
+
     #[test]
     fn white_to_str() {
         let str = to_str(Color::White);
@@ -0,0 +1,162 @@
+use crate::game::Game;
+
+/// The formats a game or position can be converted between
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Format {
+    /// Forsyth-Edwards Notation, which has only a position
+    Fen,
+    /// Portable Game Notation
+    Pgn,
+    /// Our save format, see `save::to_save`
+    Save,
+}
+
+/// The reasons why a text cannot be converted
+///
+/// ```
+/// use search_and_destroy_chess_2::convert::ConvertError;
+/// use search_and_destroy_chess_2::fen::FenError;
+///
+/// let error = ConvertError::Fen(FenError::WrongNumberOfFields(2));
+/// assert_eq!(error.to_string(), "Invalid FEN: A FEN has 6 fields separated by spaces, found 2");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ConvertError {
+    Fen(crate::fen::FenError),
+    Pgn(crate::pgn::PgnError),
+    Save(crate::headless::CommandError),
+}
+
+impl std::fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ConvertError::Fen(error) => write!(f, "Invalid FEN: {}", error),
+            ConvertError::Pgn(error) => write!(f, "Invalid PGN: {}", error),
+            ConvertError::Save(error) => write!(f, "Invalid saved game: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for ConvertError {}
+
+/// Convert a game or position from one format to another.
+/// Converting to a FEN gives the position at the end of the game
+///
+/// ```
+/// use search_and_destroy_chess_2::convert::Format;
+/// use search_and_destroy_chess_2::convert::convert;
+///
+/// let fen = convert("1. e4 *", Format::Pgn, Format::Fen).unwrap();
+/// assert_eq!(fen, "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+/// let save = convert("1. e4 *", Format::Pgn, Format::Save).unwrap();
+/// assert_eq!(save, "# Search And Destroy Chess 2\ne2e4\n");
+/// ```
+pub fn convert(text: &str, from: Format, to: Format) -> Result<String, ConvertError> {
+    let game = read_game(text, from)?;
+    Ok(write_game(&game, to))
+}
+
+/// Get a format from its name, as used on the command line
+///
+/// ```
+/// use search_and_destroy_chess_2::convert::Format;
+/// use search_and_destroy_chess_2::convert::get_format_from_str;
+///
+/// assert_eq!(get_format_from_str("pgn"), Some(Format::Pgn));
+/// assert_eq!(get_format_from_str("xml"), None);
+/// ```
+pub fn get_format_from_str(name: &str) -> Option<Format> {
+    [Format::Fen, Format::Pgn, Format::Save].iter().copied().find(|format| to_str(*format) == name)
+}
+
+/// Guess the format of a file from its extension:
+/// '.fen' is a FEN, '.pgn' is a PGN, anything else is our save format
+///
+/// ```
+/// use search_and_destroy_chess_2::convert::Format;
+/// use search_and_destroy_chess_2::convert::get_format_from_filename;
+///
+/// assert_eq!(get_format_from_filename("games/immortal.PGN"), Format::Pgn);
+/// assert_eq!(get_format_from_filename("test/kings_capture.txt"), Format::Save);
+/// ```
+pub fn get_format_from_filename(filename: &str) -> Format {
+    let extension = std::path::Path::new(filename)
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_ascii_lowercase());
+    match extension.as_deref() {
+        Some("fen") => Format::Fen,
+        Some("pgn") => Format::Pgn,
+        _ => Format::Save,
+    }
+}
+
+/// Read a game in a format. A FEN gives a game without moves
+pub fn read_game(text: &str, format: Format) -> Result<Game, ConvertError> {
+    match format {
+        Format::Fen => {
+            let position = crate::fen::position_from_fen(text.trim()).map_err(ConvertError::Fen)?;
+            let mut game = Game::new();
+            game.set_position(position);
+            Ok(game)
+        },
+        Format::Pgn => crate::pgn::game_from_pgn(text).map_err(ConvertError::Pgn),
+        Format::Save => crate::save::game_from_save(text).map_err(ConvertError::Save),
+    }
+}
+
+/// Convert a Format to its name, as used on the command line
+///
+/// ```
+/// use search_and_destroy_chess_2::convert::Format;
+/// use search_and_destroy_chess_2::convert::to_str;
+///
+/// assert_eq!(to_str(Format::Save), "save");
+/// ```
+pub fn to_str(format: Format) -> String {
+    match format {
+        Format::Fen => "fen".to_string(),
+        Format::Pgn => "pgn".to_string(),
+        Format::Save => "save".to_string(),
+    }
+}
+
+/// Write a game in a format. A FEN has the current position only
+pub fn write_game(game: &Game, format: Format) -> String {
+    match format {
+        Format::Fen => crate::fen::position_to_fen(game.get_position()),
+        Format::Pgn => crate::pgn::to_pgn(game, false),
+        Format::Save => crate::save::to_save(game),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PGN: &str = "1. e4 f5 2. Qh5 g6 3. Qxg6 h6 4. Qxe8 1-0";
+
+    #[test]
+    fn all_formats_round_trip() {
+        let formats = [Format::Fen, Format::Pgn, Format::Save];
+        let fen = convert(PGN, Format::Pgn, Format::Fen).unwrap();
+        for from in formats {
+            for to in formats {
+                let text = convert(PGN, Format::Pgn, from).unwrap();
+                let converted = convert(&text, from, to).unwrap();
+                assert_eq!(convert(&converted, to, Format::Fen).unwrap(), fen);
+            }
+        }
+    }
+    #[test]
+    fn fen_to_pgn_sets_up_the_position() {
+        let pgn = convert("4k3/8/8/8/8/8/8/4K3 b - - 0 1", Format::Fen, Format::Pgn).unwrap();
+        assert!(pgn.contains("[FEN \"4k3/8/8/8/8/8/8/4K3 b - - 0 1\"]"));
+    }
+    #[test]
+    fn errors_name_the_format() {
+        let error = convert("1. e5 *", Format::Pgn, Format::Fen).unwrap_err();
+        assert_eq!(error.to_string(), "Invalid PGN: Ply 1: Move 'e5' cannot be done in this position");
+        let error = convert("dance\n", Format::Save, Format::Fen).unwrap_err();
+        assert_eq!(error.to_string(), "Invalid saved game: Line 1: Unknown command 'dance'");
+    }
+}
@@ -0,0 +1,266 @@
+use crate::belief::Belief;
+use crate::bitboard::Bitboard;
+use crate::observation::Observation;
+use crate::piece::Piece;
+use crate::piece_type::PieceType;
+use crate::position::Position;
+use crate::square::Square;
+use rand::seq::SliceRandom;
+
+/// The number of pieces of each type a player starts with
+const STARTING_PIECES: [(PieceType, usize); 6] = [
+    (PieceType::King, 1),
+    (PieceType::Queen, 1),
+    (PieceType::Rook, 2),
+    (PieceType::Bishop, 2),
+    (PieceType::Knight, 2),
+    (PieceType::Pawn, 8),
+];
+
+/// Get the enemy pieces a player cannot see:
+/// the pieces the enemy started with, without the ones captured and the ones seen.
+/// A piece captured or seen that the enemy did not start with
+/// is taken to be a promoted pawn
+///
+/// ```
+/// use search_and_destroy_chess_2::determinization::get_hidden_pieces;
+/// use search_and_destroy_chess_2::game::Game;
+///
+/// let hidden_pieces = get_hidden_pieces(&Game::new().get_observation());
+/// assert_eq!(hidden_pieces.len(), 16);
+/// ```
+pub fn get_hidden_pieces(observation: &Observation) -> Vec<Piece> {
+    let enemy = crate::color::get_other_color(observation.get_color());
+    let mut counts = STARTING_PIECES;
+    let board = observation.get_position().get_board();
+    let seen_types: Vec<PieceType> = board.get_color_bitboard(enemy)
+        .get_squares()
+        .iter()
+        .filter_map(|square| crate::board::get_piece_at_square(board, square))
+        .map(|piece| piece.get_type())
+        .collect();
+    let captured_types = observation.get_captured().iter().map(|piece| piece.get_type());
+    for piece_type in seen_types.into_iter().chain(captured_types) {
+        // Else it is a promoted pawn
+        let index = counts.iter()
+            .position(|(counted_type, count)| *counted_type == piece_type && *count > 0)
+            .unwrap_or(5);
+        counts[index].1 = counts[index].1.saturating_sub(1);
+    }
+    counts.iter()
+        .flat_map(|(piece_type, count)| std::iter::repeat_n(Piece::new(enemy, *piece_type), *count))
+        .collect()
+}
+
+/// Get the hidden squares that must be occupied, as they block a pawn of the observer:
+/// the square in front of a pawn that cannot be seen,
+/// and, for a pawn that has not moved yet, the square two squares in front of it
+/// that cannot be seen while the square in between is empty
+///
+/// ```
+/// use search_and_destroy_chess_2::determinization::get_occupied_hidden_squares;
+/// use search_and_destroy_chess_2::game::Game;
+///
+/// assert!(get_occupied_hidden_squares(&Game::new().get_observation()).is_empty());
+/// ```
+pub fn get_occupied_hidden_squares(observation: &Observation) -> Bitboard {
+    let color = observation.get_color();
+    let board = observation.get_position().get_board();
+    let visible = observation.get_visible();
+    let (forward, starting_rank_index) = crate::move_generator::get_pawn_direction(color);
+    let mut occupied = Bitboard::empty();
+    for square in board.get_piece_bitboard(color, PieceType::Pawn).get_squares() {
+        let one_step = match crate::square::get_square_at_offset(&square, 0, forward) {
+            Some(one_step) => one_step,
+            None => continue,
+        };
+        if !visible.contains(&one_step) {
+            occupied.add(&one_step);
+            continue;
+        }
+        if square.get_rank_index() != starting_rank_index || board.get_occupied().contains(&one_step) {
+            continue;
+        }
+        if let Some(two_steps) = crate::square::get_square_at_offset(&square, 0, 2 * forward) {
+            if !visible.contains(&two_steps) {
+                occupied.add(&two_steps);
+            }
+        }
+    }
+    occupied
+}
+
+/// Sample a position that is possible given what a player has observed:
+/// the hidden enemy pieces are put on random squares the player cannot see.
+/// The squares that must be occupied are filled first.
+/// The enemy cannot castle in the position, as the player cannot know if it can
+///
+/// ```
+/// use search_and_destroy_chess_2::color::Color;
+/// use search_and_destroy_chess_2::determinization::sample_position;
+/// use search_and_destroy_chess_2::game::Game;
+///
+/// let observation = Game::new().get_observation();
+/// let position = sample_position(&observation, &mut rand::thread_rng());
+/// assert_eq!(position.get_board().get_color_bitboard(Color::Black).count(), 16);
+/// assert_eq!(position.get_pseudo_legal_moves(&observation.get_rules()).len(), 20);
+/// ```
+pub fn sample_position<R: rand::Rng>(observation: &Observation, rng: &mut R) -> Position {
+    let hidden_pieces = get_hidden_pieces(observation);
+    let free_squares: Vec<Square> = (!observation.get_visible()).get_squares();
+    let occupied_squares = get_occupied_hidden_squares(observation).get_squares();
+    sample_position_with(observation, hidden_pieces, &occupied_squares, rng, |_, _| free_squares.clone())
+}
+
+/// Sample a position, in which each hidden piece is on a square
+/// the player believes it might be on
+///
+/// ```
+/// use search_and_destroy_chess_2::belief::Belief;
+/// use search_and_destroy_chess_2::board::get_piece_at_square;
+/// use search_and_destroy_chess_2::color::Color;
+/// use search_and_destroy_chess_2::determinization::sample_position_from_belief;
+/// use search_and_destroy_chess_2::game::Game;
+/// use search_and_destroy_chess_2::square::Square;
+///
+/// let observation = Game::new().get_observation();
+/// let mut belief = Belief::new(Color::White);
+/// belief.update(&observation);
+/// let position = sample_position_from_belief(&observation, &belief, &mut rand::thread_rng());
+/// assert_eq!(position.get_board(), &search_and_destroy_chess_2::board::Board::new());
+/// ```
+pub fn sample_position_from_belief<R: rand::Rng>(observation: &Observation, belief: &Belief, rng: &mut R) -> Position {
+    let board = observation.get_position().get_board();
+    let enemy = crate::color::get_other_color(observation.get_color());
+    let mut piece_beliefs: Vec<_> = belief.get_pieces().iter().collect();
+
+    // The pieces seen are on the board already
+    for square in board.get_color_bitboard(enemy).get_squares() {
+        let piece = crate::board::get_piece_at_square(board, &square).unwrap();
+        let found = piece_beliefs.iter()
+            .position(|piece_belief| *piece_belief.get_piece() == piece && piece_belief.get_squares().contains(&square))
+            .or_else(|| piece_beliefs.iter().position(|piece_belief| piece_belief.get_squares().contains(&square)));
+        if let Some(index) = found {
+            piece_beliefs.remove(index);
+        }
+    }
+
+    let hidden = !observation.get_visible();
+    let hidden_pieces = piece_beliefs.iter().map(|piece_belief| piece_belief.get_piece().clone()).collect();
+    let occupied_squares = get_occupied_hidden_squares(observation).get_squares();
+    sample_position_with(observation, hidden_pieces, &occupied_squares, rng, |index, _| {
+        (piece_beliefs[index].get_squares() & hidden).get_squares()
+    })
+}
+
+/// Sample a position, given the hidden pieces, the squares that must be occupied,
+/// and a function giving the squares each hidden piece may be on.
+/// A piece for which none of these squares is free is put on any free square that is not seen
+pub fn sample_position_with<R: rand::Rng, F: Fn(usize, &Piece) -> Vec<Square>>(
+    observation: &Observation,
+    hidden_pieces: Vec<Piece>,
+    occupied_squares: &[Square],
+    rng: &mut R,
+    get_candidate_squares: F
+) -> Position {
+    let mut board = observation.get_position().get_board().clone();
+    let mut indices: Vec<usize> = (0..hidden_pieces.len()).collect();
+    indices.shuffle(rng);
+
+    // Fill the squares that must be occupied, with pieces that can be there
+    for square in occupied_squares {
+        let found = indices.iter().position(|index| {
+            let piece = &hidden_pieces[*index];
+            piece.get_type() != PieceType::King
+                && can_stand_at(piece, square)
+                && get_candidate_squares(*index, piece).contains(square)
+        });
+        if let Some(position) = found {
+            let index = indices.remove(position);
+            board.set_piece_at_square(square, Some(hidden_pieces[index].clone()));
+        }
+    }
+
+    // Put the king first, as it must be on the board
+    indices.sort_by_key(|index| hidden_pieces[*index].get_type() != PieceType::King);
+    for index in indices {
+        let piece = &hidden_pieces[index];
+        let free = crate::move_generator::get_squares_it_can_stand_on(piece, !board.get_occupied());
+        let is_free = |square: &Square| free.contains(square);
+        let mut candidates: Vec<Square> = get_candidate_squares(index, piece)
+            .into_iter()
+            .filter(is_free)
+            .collect();
+        if candidates.is_empty() {
+            candidates = (!observation.get_visible()).get_squares().into_iter().filter(is_free).collect();
+        }
+        if let Some(square) = candidates.choose(rng) {
+            board.set_piece_at_square(square, Some(piece.clone()));
+        }
+    }
+
+    let fogged_position = observation.get_position();
+    let mut position = Position::from_board(board, observation.get_color(), fogged_position.get_castling_rights());
+    position.set_en_passant(fogged_position.get_en_passant());
+    position.set_fullmove_number(fogged_position.get_fullmove_number());
+    position
+}
+
+/// Detect if a piece can stand on a square
+fn can_stand_at(piece: &Piece, square: &Square) -> bool {
+    !crate::move_generator::get_squares_it_can_stand_on(piece, Bitboard::from_square(square)).is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+    use rand::SeedableRng;
+
+    fn create_observation(fen: &str) -> Observation {
+        let position = crate::fen::position_from_fen(fen).unwrap();
+        Observation::new(&position, &crate::rules::Rules::new())
+    }
+
+    #[test]
+    fn samples_keep_the_moves_of_the_observer() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let fens = [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2",
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        ];
+        for fen in fens {
+            let observation = create_observation(fen);
+            let real_moves = crate::fen::position_from_fen(fen).unwrap().get_pseudo_legal_moves(&observation.get_rules());
+            for _ in 0..20 {
+                let position = sample_position(&observation, &mut rng);
+                let moves = position.get_pseudo_legal_moves(&observation.get_rules());
+                let same = moves.len() == real_moves.len()
+                    && moves.iter().zip(&real_moves).all(|(a, b)| crate::chess_move::is_same_move(a, b));
+                assert!(same, "{} sampled as {}", fen, crate::fen::position_to_fen(&position));
+                let visible = crate::board::get_visible_bitboard(position.get_board(), observation.get_color());
+                assert_eq!(visible, observation.get_visible());
+            }
+        }
+    }
+    #[test]
+    fn captured_pieces_are_not_sampled() {
+        let game = crate::save::game_from_save("e2e4\nd7d5\ne4d5\ng8f6\n").unwrap();
+        let hidden_pieces = get_hidden_pieces(&game.get_observation());
+        let n_pawns = hidden_pieces.iter().filter(|piece| piece.get_type() == PieceType::Pawn).count();
+        assert_eq!(n_pawns, 7);
+        assert_eq!(hidden_pieces.len(), 15);
+    }
+    #[test]
+    fn pawns_are_not_on_the_last_ranks() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let observation = create_observation("4k3/8/8/8/8/8/8/4K3 w - - 0 1");
+        for _ in 0..20 {
+            let board = sample_position(&observation, &mut rng).get_board().clone();
+            let pawns = board.get_piece_bitboard(Color::Black, PieceType::Pawn).get_squares();
+            assert!(pawns.iter().all(|square| (1..7).contains(&square.get_rank_index())));
+            assert_eq!(board.get_piece_bitboard(Color::Black, PieceType::King).count(), 1);
+        }
+    }
+}
@@ -0,0 +1,431 @@
+use crate::bitboard::Bitboard;
+use crate::board::Board;
+use crate::color::Color;
+use crate::piece_type::PieceType;
+use crate::square::Square;
+
+/// The piece types, in the order of their keys in a weights file
+const PIECE_TYPES: [PieceType; 6] = [
+    PieceType::Pawn,
+    PieceType::Knight,
+    PieceType::Bishop,
+    PieceType::Rook,
+    PieceType::Queen,
+    PieceType::King,
+];
+
+/// The piece-square tables, in centipawns, for White,
+/// with the eighth rank first, as a board is drawn
+const PAWN_TABLE: [i32; 64] = [
+     0,  0,   0,   0,   0,   0,  0,  0,
+    50, 50,  50,  50,  50,  50, 50, 50,
+    10, 10,  20,  30,  30,  20, 10, 10,
+     5,  5,  10,  25,  25,  10,  5,  5,
+     0,  0,   0,  20,  20,   0,  0,  0,
+     5, -5, -10,   0,   0, -10, -5,  5,
+     5, 10,  10, -20, -20,  10, 10,  5,
+     0,  0,   0,   0,   0,   0,  0,  0,
+];
+const KNIGHT_TABLE: [i32; 64] = [
+    -50, -40, -30, -30, -30, -30, -40, -50,
+    -40, -20,   0,   0,   0,   0, -20, -40,
+    -30,   0,  10,  15,  15,  10,   0, -30,
+    -30,   5,  15,  20,  20,  15,   5, -30,
+    -30,   0,  15,  20,  20,  15,   0, -30,
+    -30,   5,  10,  15,  15,  10,   5, -30,
+    -40, -20,   0,   5,   5,   0, -20, -40,
+    -50, -40, -30, -30, -30, -30, -40, -50,
+];
+const BISHOP_TABLE: [i32; 64] = [
+    -20, -10, -10, -10, -10, -10, -10, -20,
+    -10,   0,   0,   0,   0,   0,   0, -10,
+    -10,   0,   5,  10,  10,   5,   0, -10,
+    -10,   5,   5,  10,  10,   5,   5, -10,
+    -10,   0,  10,  10,  10,  10,   0, -10,
+    -10,  10,  10,  10,  10,  10,  10, -10,
+    -10,   5,   0,   0,   0,   0,   5, -10,
+    -20, -10, -10, -10, -10, -10, -10, -20,
+];
+const ROOK_TABLE: [i32; 64] = [
+     0,  0,  0,  0,  0,  0,  0,  0,
+     5, 10, 10, 10, 10, 10, 10,  5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+     0,  0,  0,  5,  5,  0,  0,  0,
+];
+const QUEEN_TABLE: [i32; 64] = [
+    -20, -10, -10, -5, -5, -10, -10, -20,
+    -10,   0,   0,  0,  0,   0,   0, -10,
+    -10,   0,   5,  5,  5,   5,   0, -10,
+     -5,   0,   5,  5,  5,   5,   0,  -5,
+      0,   0,   5,  5,  5,   5,   0,  -5,
+    -10,   5,   5,  5,  5,   5,   0, -10,
+    -10,   0,   5,  0,  0,   0,   0, -10,
+    -20, -10, -10, -5, -5, -10, -10, -20,
+];
+const KING_TABLE: [i32; 64] = [
+    -30, -40, -40, -50, -50, -40, -40, -30,
+    -30, -40, -40, -50, -50, -40, -40, -30,
+    -30, -40, -40, -50, -50, -40, -40, -30,
+    -30, -40, -40, -50, -50, -40, -40, -30,
+    -20, -30, -30, -40, -40, -30, -30, -20,
+    -10, -20, -20, -20, -20, -20, -20, -10,
+     20,  20,   0,   0,   0,   0,  20,  20,
+     20,  30,  10,   0,   0,  10,  30,  20,
+];
+
+/// How much each part of the evaluation counts.
+/// The material values are in centipawns,
+/// the piece-square tables count in percent,
+/// king safety counts per piece shielding the king and per square around it the enemy sees,
+/// and mobility counts per move
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Weights {
+    material: [i32; 6],
+    piece_square: i32,
+    king_safety: i32,
+    mobility: i32,
+}
+
+impl Weights {
+
+    /// Create the default weights
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::evaluation::Weights;
+    /// use search_and_destroy_chess_2::piece_type::PieceType;
+    ///
+    /// let weights = Weights::new();
+    /// assert_eq!(weights.get_material(PieceType::Pawn), 100);
+    /// assert_eq!(weights.get_material(PieceType::Queen), 900);
+    /// assert_eq!(weights.get_piece_square(), 100);
+    /// ```
+    pub fn new() -> Weights {
+        Weights {
+            material: [100, 320, 330, 500, 900, 20000],
+            piece_square: 100,
+            king_safety: 10,
+            mobility: 5,
+        }
+    }
+
+    /// Get the weight of king safety
+    pub fn get_king_safety(&self) -> i32 { self.king_safety }
+
+    /// Get the value of a piece type, in centipawns
+    pub fn get_material(&self, piece_type: PieceType) -> i32 { self.material[get_index(piece_type)] }
+
+    /// Get the weight of mobility
+    pub fn get_mobility(&self) -> i32 { self.mobility }
+
+    /// Get how much the piece-square tables count, in percent
+    pub fn get_piece_square(&self) -> i32 { self.piece_square }
+
+    /// Set the weight of king safety
+    pub fn set_king_safety(&mut self, king_safety: i32) { self.king_safety = king_safety }
+
+    /// Set the value of a piece type, in centipawns
+    pub fn set_material(&mut self, piece_type: PieceType, value: i32) { self.material[get_index(piece_type)] = value }
+
+    /// Set the weight of mobility
+    pub fn set_mobility(&mut self, mobility: i32) { self.mobility = mobility }
+
+    /// Set how much the piece-square tables count, in percent
+    pub fn set_piece_square(&mut self, piece_square: i32) { self.piece_square = piece_square }
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The reasons why a weights file cannot be read
+///
+/// ```
+/// use search_and_destroy_chess_2::evaluation::WeightsError;
+///
+/// let error = WeightsError::UnknownKey { line: 3, key: "speed".to_string() };
+/// assert_eq!(error.to_string(), "Line 3: unknown weight 'speed'");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum WeightsError {
+    InvalidLine { line: usize, text: String },
+    InvalidValue { line: usize, key: String, value: String },
+    UnknownKey { line: usize, key: String },
+}
+
+impl std::fmt::Display for WeightsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            WeightsError::InvalidLine { line, text } => write!(f, "Line {}: expected 'key = value', got '{}'", line, text),
+            WeightsError::InvalidValue { line, key, value } => write!(f, "Line {}: invalid value '{}' for '{}'", line, value, key),
+            WeightsError::UnknownKey { line, key } => write!(f, "Line {}: unknown weight '{}'", line, key),
+        }
+    }
+}
+
+impl std::error::Error for WeightsError {}
+
+/// The parts of the evaluation of a board, in centipawns,
+/// each being how much better it is for a color than for its enemy
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Evaluation {
+    material: i32,
+    piece_square: i32,
+    king_safety: i32,
+    mobility: i32,
+}
+
+impl Evaluation {
+
+    /// Get the score for king safety
+    pub fn get_king_safety(&self) -> i32 { self.king_safety }
+
+    /// Get the score for material
+    pub fn get_material(&self) -> i32 { self.material }
+
+    /// Get the score for mobility
+    pub fn get_mobility(&self) -> i32 { self.mobility }
+
+    /// Get the score for the piece-square tables
+    pub fn get_piece_square(&self) -> i32 { self.piece_square }
+
+    /// Get the sum of all scores
+    pub fn get_total(&self) -> i32 { self.material + self.piece_square + self.king_safety + self.mobility }
+}
+
+/// Evaluate a board for a color, in centipawns:
+/// positive is good for the color, negative is good for its enemy
+///
+/// ```
+/// use search_and_destroy_chess_2::board::Board;
+/// use search_and_destroy_chess_2::color::Color;
+/// use search_and_destroy_chess_2::evaluation::Weights;
+/// use search_and_destroy_chess_2::evaluation::evaluate;
+///
+/// assert_eq!(evaluate(&Board::new(), Color::White, &Weights::new()), 0);
+/// ```
+pub fn evaluate(board: &Board, color: Color, weights: &Weights) -> i32 {
+    get_evaluation(board, color, weights).get_total()
+}
+
+/// Get the parts of the evaluation of a board for a color
+///
+/// ```
+/// use search_and_destroy_chess_2::color::Color;
+/// use search_and_destroy_chess_2::evaluation::Weights;
+/// use search_and_destroy_chess_2::evaluation::get_evaluation;
+/// use search_and_destroy_chess_2::fen::position_from_fen;
+///
+/// // White has an extra queen
+/// let position = position_from_fen("4k3/8/8/8/8/8/8/3QK3 w - - 0 1").unwrap();
+/// let evaluation = get_evaluation(position.get_board(), Color::White, &Weights::new());
+/// assert_eq!(evaluation.get_material(), 900);
+/// assert!(evaluation.get_mobility() > 0);
+/// assert_eq!(get_evaluation(position.get_board(), Color::Black, &Weights::new()).get_material(), -900);
+/// ```
+pub fn get_evaluation(board: &Board, color: Color, weights: &Weights) -> Evaluation {
+    let enemy = crate::color::get_other_color(color);
+    Evaluation {
+        material: get_material_score(board, color, weights) - get_material_score(board, enemy, weights),
+        piece_square: (get_piece_square_score(board, color) - get_piece_square_score(board, enemy)) * weights.piece_square / 100,
+        king_safety: (get_king_safety_score(board, color) - get_king_safety_score(board, enemy)) * weights.king_safety,
+        mobility: (get_mobility_score(board, color) - get_mobility_score(board, enemy)) * weights.mobility,
+    }
+}
+
+/// Read weights from the text of a weights file,
+/// with one 'key = value' per line, where empty lines and lines starting with '#' are skipped.
+/// The keys are the piece types, 'piece_square', 'king_safety' and 'mobility'.
+/// Weights that are not given keep their default
+///
+/// ```
+/// use search_and_destroy_chess_2::evaluation::weights_from_str;
+/// use search_and_destroy_chess_2::piece_type::PieceType;
+///
+/// let weights = weights_from_str("# Knights are worth more\nknight = 350\nmobility = 0\n").unwrap();
+/// assert_eq!(weights.get_material(PieceType::Knight), 350);
+/// assert_eq!(weights.get_material(PieceType::Bishop), 330);
+/// assert_eq!(weights.get_mobility(), 0);
+/// ```
+pub fn weights_from_str(text: &str) -> Result<Weights, WeightsError> {
+    let mut weights = Weights::new();
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let (key, value) = trimmed.split_once('=')
+            .ok_or_else(|| WeightsError::InvalidLine { line: line_number, text: trimmed.to_string() })?;
+        let (key, value) = (key.trim(), value.trim());
+        let number: i32 = value.parse()
+            .map_err(|_| WeightsError::InvalidValue { line: line_number, key: key.to_string(), value: value.to_string() })?;
+        match key {
+            "piece_square" => weights.piece_square = number,
+            "king_safety" => weights.king_safety = number,
+            "mobility" => weights.mobility = number,
+            _ => {
+                let piece_type = PIECE_TYPES.iter()
+                    .find(|piece_type| get_key(**piece_type) == key)
+                    .ok_or_else(|| WeightsError::UnknownKey { line: line_number, key: key.to_string() })?;
+                weights.set_material(*piece_type, number);
+            },
+        }
+    }
+    Ok(weights)
+}
+
+/// Convert weights to the text of a weights file
+///
+/// ```
+/// use search_and_destroy_chess_2::evaluation::Weights;
+/// use search_and_destroy_chess_2::evaluation::to_str;
+/// use search_and_destroy_chess_2::evaluation::weights_from_str;
+///
+/// let weights = Weights::new();
+/// assert!(to_str(&weights).contains("queen = 900\n"));
+/// assert_eq!(weights_from_str(&to_str(&weights)), Ok(weights));
+/// ```
+pub fn to_str(weights: &Weights) -> String {
+    let mut text = String::from("# Material, in centipawns\n");
+    for piece_type in PIECE_TYPES {
+        text += &format!("{} = {}\n", get_key(piece_type), weights.get_material(piece_type));
+    }
+    text += "# How much the piece-square tables count, in percent\n";
+    text += &format!("piece_square = {}\n", weights.piece_square);
+    text += "# Per piece shielding the king, minus per square around the king the enemy sees\n";
+    text += &format!("king_safety = {}\n", weights.king_safety);
+    text += "# Per move\n";
+    text += &format!("mobility = {}\n", weights.mobility);
+    text
+}
+
+/// Get the index of a piece type in the material values
+fn get_index(piece_type: PieceType) -> usize {
+    PIECE_TYPES.iter().position(|other| *other == piece_type).unwrap()
+}
+
+/// Get the key of a piece type in a weights file
+fn get_key(piece_type: PieceType) -> &'static str {
+    match piece_type {
+        PieceType::Bishop => "bishop",
+        PieceType::King => "king",
+        PieceType::Knight => "knight",
+        PieceType::Pawn => "pawn",
+        PieceType::Queen => "queen",
+        PieceType::Rook => "rook",
+    }
+}
+
+/// Get the value of the pieces of a color
+fn get_material_score(board: &Board, color: Color, weights: &Weights) -> i32 {
+    PIECE_TYPES.iter()
+        .map(|piece_type| board.get_piece_bitboard(color, *piece_type).count() as i32 * weights.get_material(*piece_type))
+        .sum()
+}
+
+/// Get how well the pieces of a color are placed
+fn get_piece_square_score(board: &Board, color: Color) -> i32 {
+    let mut score = 0;
+    for piece_type in PIECE_TYPES {
+        let table = match piece_type {
+            PieceType::Bishop => &BISHOP_TABLE,
+            PieceType::King => &KING_TABLE,
+            PieceType::Knight => &KNIGHT_TABLE,
+            PieceType::Pawn => &PAWN_TABLE,
+            PieceType::Queen => &QUEEN_TABLE,
+            PieceType::Rook => &ROOK_TABLE,
+        };
+        for square in board.get_piece_bitboard(color, piece_type).get_squares() {
+            score += table[get_table_index(&square, color)];
+        }
+    }
+    score
+}
+
+/// Get the index of a square in a piece-square table,
+/// which is mirrored for Black
+fn get_table_index(square: &Square, color: Color) -> usize {
+    let row = match color {
+        Color::White => 7 - square.get_rank_index(),
+        Color::Black => square.get_rank_index(),
+    };
+    row * 8 + square.get_file_index()
+}
+
+/// Get how safe the king of a color is under the fog of war:
+/// the own pieces around the king shield it and hide it,
+/// and each square around it the enemy sees, including its own, is a way to attack it
+fn get_king_safety_score(board: &Board, color: Color) -> i32 {
+    let king_square = match board.get_piece_bitboard(color, PieceType::King).get_squares().first() {
+        Some(square) => *square,
+        None => return 0,
+    };
+    let around = crate::bitboard::get_king_attacks(&king_square);
+    let shield = (around & board.get_color_bitboard(color)).count() as i32;
+    let enemy_visible = crate::board::get_visible_bitboard(board, crate::color::get_other_color(color));
+    let exposure = ((around | Bitboard::from_square(&king_square)) & enemy_visible).count() as i32;
+    shield - exposure
+}
+
+/// Get the number of moves of a color
+fn get_mobility_score(board: &Board, color: Color) -> i32 {
+    crate::move_generator::get_pseudo_legal_moves(board, color).len() as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate_fen(fen: &str, color: Color) -> Evaluation {
+        let position = crate::fen::position_from_fen(fen).unwrap();
+        get_evaluation(position.get_board(), color, &Weights::new())
+    }
+
+    #[test]
+    fn evaluation_is_symmetric() {
+        let fen = "r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4";
+        let white = evaluate_fen(fen, Color::White);
+        let black = evaluate_fen(fen, Color::Black);
+        assert_eq!(white.get_total(), -black.get_total());
+        assert_eq!(white.get_material(), 0);
+    }
+    #[test]
+    fn central_knight_is_better() {
+        let center = evaluate_fen("4k3/8/8/8/3N4/8/8/4K3 w - - 0 1", Color::White);
+        let corner = evaluate_fen("4k3/8/8/8/8/8/8/N3K3 w - - 0 1", Color::White);
+        assert!(center.get_piece_square() > corner.get_piece_square());
+        assert!(center.get_mobility() > corner.get_mobility());
+    }
+    #[test]
+    fn exposed_king_is_less_safe() {
+        let shielded = evaluate_fen("4k3/8/8/8/8/8/5PPP/4rRK1 w - - 0 1", Color::White);
+        let exposed = evaluate_fen("4k3/8/8/8/8/8/8/4r1K1 w - - 0 1", Color::White);
+        assert!(shielded.get_king_safety() > exposed.get_king_safety());
+    }
+    #[test]
+    fn missing_king_loses() {
+        assert!(evaluate_fen("8/8/8/8/8/8/8/QQQQK3 w - - 0 1", Color::White).get_total() > 10000);
+        assert!(evaluate_fen("8/8/8/8/8/8/8/QQQQK3 w - - 0 1", Color::Black).get_total() < -10000);
+    }
+    #[test]
+    fn weights_errors_are_given_per_line() {
+        assert_eq!(weights_from_str("pawn = 100\nrook 500\n"), Err(WeightsError::InvalidLine { line: 2, text: "rook 500".to_string() }));
+        assert_eq!(
+            weights_from_str("pawn = many\n"),
+            Err(WeightsError::InvalidValue { line: 1, key: "pawn".to_string(), value: "many".to_string() })
+        );
+        assert_eq!(weights_from_str("\n\nspeed = 3\n"), Err(WeightsError::UnknownKey { line: 3, key: "speed".to_string() }));
+    }
+    #[test]
+    fn weights_change_the_evaluation() {
+        let board = crate::fen::position_from_fen("4k3/8/8/8/8/8/8/3QK3 w - - 0 1").unwrap().get_board().clone();
+        let weights = weights_from_str("queen = 1000\npiece_square = 0\nking_safety = 0\nmobility = 0\n").unwrap();
+        assert_eq!(evaluate(&board, Color::White, &weights), 1000);
+    }
+}
@@ -0,0 +1,353 @@
+use crate::board::Board;
+use crate::castling_rights::CastlingRights;
+use crate::color::Color;
+use crate::piece::Piece;
+use crate::position::Position;
+use crate::rank::Rank;
+use crate::square::Square;
+
+/// The FEN of the starting position
+pub const STARTING_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/// The reasons why a string is not a valid FEN.
+/// Each error tells which part of the FEN is wrong, so it can be shown to a user
+///
+/// ```
+/// use search_and_destroy_chess_2::fen::FenError;
+/// use search_and_destroy_chess_2::fen::position_from_fen;
+///
+/// let error = position_from_fen("8/8/8/8/8/8/8/8 x - - 0 1").unwrap_err();
+/// assert_eq!(error, FenError::InvalidSideToMove("x".to_string()));
+/// assert_eq!(error.to_string(), "Invalid side to move 'x', expected 'w' or 'b'");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum FenError {
+    WrongNumberOfFields(usize),
+    WrongNumberOfRanks(usize),
+    WrongNumberOfFiles { rank: usize, files: usize },
+    InvalidPiece { rank: usize, letter: char },
+    InvalidSideToMove(String),
+    InvalidCastlingRights(String),
+    InvalidEnPassant(String),
+    InvalidHalfmoveClock(String),
+    InvalidFullmoveNumber(String),
+}
+
+impl std::fmt::Display for FenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FenError::WrongNumberOfFields(n_fields) => write!(f, "A FEN has 6 fields separated by spaces, found {}", n_fields),
+            FenError::WrongNumberOfRanks(n_ranks) => write!(f, "A board has 8 ranks separated by '/', found {}", n_ranks),
+            FenError::WrongNumberOfFiles { rank, files } => write!(f, "Rank {} has {} files, expected 8", rank, files),
+            FenError::InvalidPiece { rank, letter } => write!(f, "Rank {} has an invalid piece '{}'", rank, letter),
+            FenError::InvalidSideToMove(field) => write!(f, "Invalid side to move '{}', expected 'w' or 'b'", field),
+            FenError::InvalidCastlingRights(field) => write!(f, "Invalid castling rights '{}', expected '-' or letters from 'KQkq'", field),
+            FenError::InvalidEnPassant(field) => write!(f, "Invalid en-passant target '{}', expected '-' or a square on rank 3 or 6", field),
+            FenError::InvalidHalfmoveClock(field) => write!(f, "Invalid halfmove clock '{}', expected a number", field),
+            FenError::InvalidFullmoveNumber(field) => write!(f, "Invalid fullmove number '{}', expected a number from 1", field),
+        }
+    }
+}
+
+impl std::error::Error for FenError {}
+
+/// Read the pieces on a board from the first field of a FEN,
+/// starting at the eighth rank
+///
+/// ```
+/// use search_and_destroy_chess_2::board::Board;
+/// use search_and_destroy_chess_2::fen::board_from_fen;
+///
+/// let board = board_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR").unwrap();
+/// assert_eq!(board, Board::new());
+/// assert!(board_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP").is_err());
+/// ```
+pub fn board_from_fen(placement: &str) -> Result<Board, FenError> {
+    let fen_ranks: Vec<&str> = placement.split('/').collect();
+    if fen_ranks.len() != 8 {
+        return Err(FenError::WrongNumberOfRanks(fen_ranks.len()));
+    }
+    let mut ranks = Vec::new();
+    for (index, fen_rank) in fen_ranks.iter().rev().enumerate() {
+        ranks.push(rank_from_fen(fen_rank, index + 1)?);
+    }
+    Ok(Board::from_ranks(ranks))
+}
+
+/// Write the pieces on a board as the first field of a FEN
+///
+/// ```
+/// use search_and_destroy_chess_2::board::Board;
+/// use search_and_destroy_chess_2::fen::board_to_fen;
+///
+/// assert_eq!(board_to_fen(&Board::new()), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR");
+/// ```
+pub fn board_to_fen(board: &Board) -> String {
+    let fen_ranks: Vec<String> = board.get_ranks().iter().rev().map(rank_to_fen).collect();
+    fen_ranks.join("/")
+}
+
+/// Read the castling rights from the third field of a FEN
+///
+/// ```
+/// use search_and_destroy_chess_2::color::Color;
+/// use search_and_destroy_chess_2::fen::castling_rights_from_fen;
+///
+/// let castling_rights = castling_rights_from_fen("Kq").unwrap();
+/// assert!(castling_rights.can_castle_king_side(Color::White));
+/// assert!(!castling_rights.can_castle_queen_side(Color::White));
+/// assert!(!castling_rights.can_castle_king_side(Color::Black));
+/// assert!(castling_rights.can_castle_queen_side(Color::Black));
+/// ```
+pub fn castling_rights_from_fen(field: &str) -> Result<CastlingRights, FenError> {
+    let mut castling_rights = CastlingRights::none();
+    if field == "-" {
+        return Ok(castling_rights);
+    }
+    if field.is_empty() {
+        return Err(FenError::InvalidCastlingRights(field.to_string()));
+    }
+    for letter in field.chars() {
+        match letter {
+            'K' => castling_rights.set_king_side(Color::White, true),
+            'Q' => castling_rights.set_queen_side(Color::White, true),
+            'k' => castling_rights.set_king_side(Color::Black, true),
+            'q' => castling_rights.set_queen_side(Color::Black, true),
+            _ => return Err(FenError::InvalidCastlingRights(field.to_string())),
+        }
+    }
+    Ok(castling_rights)
+}
+
+/// Write the castling rights as the third field of a FEN
+///
+/// ```
+/// use search_and_destroy_chess_2::castling_rights::CastlingRights;
+/// use search_and_destroy_chess_2::fen::castling_rights_to_fen;
+///
+/// assert_eq!(castling_rights_to_fen(&CastlingRights::new()), "KQkq");
+/// assert_eq!(castling_rights_to_fen(&CastlingRights::none()), "-");
+/// ```
+pub fn castling_rights_to_fen(castling_rights: &CastlingRights) -> String {
+    let mut field = String::new();
+    for color in [Color::White, Color::Black] {
+        if castling_rights.can_castle_king_side(color) {
+            field.push(get_piece_letter(&Piece::new(color, crate::piece_type::PieceType::King)));
+        }
+        if castling_rights.can_castle_queen_side(color) {
+            field.push(get_piece_letter(&Piece::new(color, crate::piece_type::PieceType::Queen)));
+        }
+    }
+    match field.is_empty() {
+        true => "-".to_string(),
+        false => field,
+    }
+}
+
+/// Read a position from a FEN, e.g. to set up a test position
+///
+/// ```
+/// use search_and_destroy_chess_2::fen::position_from_fen;
+/// use search_and_destroy_chess_2::position::Position;
+/// use search_and_destroy_chess_2::square::Square;
+///
+/// let position = position_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+/// assert_eq!(position, Position::new());
+///
+/// let position = position_from_fen("rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3").unwrap();
+/// assert_eq!(position.get_en_passant(), Some(Square::new("d6")));
+/// assert_eq!(position.get_fullmove_number(), 3);
+/// ```
+pub fn position_from_fen(fen: &str) -> Result<Position, FenError> {
+    let fields: Vec<&str> = fen.split_whitespace().collect();
+    if fields.len() != 6 {
+        return Err(FenError::WrongNumberOfFields(fields.len()));
+    }
+    let board = board_from_fen(fields[0])?;
+    let side_to_move = match fields[1] {
+        "w" => Color::White,
+        "b" => Color::Black,
+        _ => return Err(FenError::InvalidSideToMove(fields[1].to_string())),
+    };
+    let castling_rights = castling_rights_from_fen(fields[2])?;
+    let en_passant = en_passant_from_fen(fields[3])?;
+    let halfmove_clock = match fields[4].parse::<u32>() {
+        Ok(halfmove_clock) => halfmove_clock,
+        Err(_) => return Err(FenError::InvalidHalfmoveClock(fields[4].to_string())),
+    };
+    let fullmove_number = match fields[5].parse::<u32>() {
+        Ok(fullmove_number) if fullmove_number >= 1 => fullmove_number,
+        _ => return Err(FenError::InvalidFullmoveNumber(fields[5].to_string())),
+    };
+    let mut position = Position::from_board(board, side_to_move, castling_rights);
+    position.set_en_passant(en_passant);
+    position.set_halfmove_clock(halfmove_clock);
+    position.set_fullmove_number(fullmove_number);
+    Ok(position)
+}
+
+/// Write a position as a FEN, e.g. to describe it in a bug report
+///
+/// ```
+/// use search_and_destroy_chess_2::chess_move::Move;
+/// use search_and_destroy_chess_2::fen::position_to_fen;
+/// use search_and_destroy_chess_2::position::Position;
+/// use search_and_destroy_chess_2::square::Square;
+///
+/// let mut position = Position::new();
+/// position.apply_move(&Move::new(Square::new("e2"), Square::new("e4")));
+/// assert_eq!(position_to_fen(&position), "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+/// ```
+pub fn position_to_fen(position: &Position) -> String {
+    let side_to_move = match position.get_side_to_move() {
+        Color::White => "w",
+        Color::Black => "b",
+    };
+    let en_passant = match position.get_en_passant() {
+        Some(square) => square.get(),
+        None => "-".to_string(),
+    };
+    format!(
+        "{} {} {} {} {} {}",
+        board_to_fen(position.get_board()),
+        side_to_move,
+        castling_rights_to_fen(&position.get_castling_rights()),
+        en_passant,
+        position.get_halfmove_clock(),
+        position.get_fullmove_number()
+    )
+}
+
+/// Read the en-passant target from the fourth field of a FEN
+fn en_passant_from_fen(field: &str) -> Result<Option<Square>, FenError> {
+    if field == "-" {
+        return Ok(None);
+    }
+    match field.parse::<Square>() {
+        Ok(square) if square.get_rank() == 3 || square.get_rank() == 6 => Ok(Some(square)),
+        _ => Err(FenError::InvalidEnPassant(field.to_string())),
+    }
+}
+
+/// Get the letter of a piece in a FEN: uppercase for white, lowercase for black
+///
+/// ```
+/// use search_and_destroy_chess_2::fen::get_piece_letter;
+/// use search_and_destroy_chess_2::piece::create_black_knight;
+/// use search_and_destroy_chess_2::piece::create_white_queen;
+///
+/// assert_eq!(get_piece_letter(&create_white_queen()), 'Q');
+/// assert_eq!(get_piece_letter(&create_black_knight()), 'n');
+/// ```
+pub fn get_piece_letter(piece: &Piece) -> char {
+    let letter = crate::piece_type::to_char(piece.get_type());
+    match piece.get_color() {
+        Color::White => letter,
+        Color::Black => letter.to_ascii_lowercase(),
+    }
+}
+
+/// Read a rank from a FEN, where digits are the number of empty squares.
+/// The rank number, from 1 to 8, is used in errors
+fn rank_from_fen(fen_rank: &str, rank_number: usize) -> Result<Rank, FenError> {
+    let mut pieces: Vec<Option<Piece>> = Vec::new();
+    for letter in fen_rank.chars() {
+        match letter.to_digit(10) {
+            Some(n_empty) if (1..=8).contains(&n_empty) => {
+                for _ in 0..n_empty {
+                    pieces.push(None);
+                }
+            },
+            Some(_) => return Err(FenError::InvalidPiece { rank: rank_number, letter }),
+            None => {
+                let piece_type = match crate::piece_type::from_char(letter) {
+                    Some(piece_type) => piece_type,
+                    None => return Err(FenError::InvalidPiece { rank: rank_number, letter }),
+                };
+                let color = match letter.is_ascii_uppercase() {
+                    true => Color::White,
+                    false => Color::Black,
+                };
+                pieces.push(Some(Piece::new(color, piece_type)));
+            },
+        }
+    }
+    if pieces.len() != 8 {
+        return Err(FenError::WrongNumberOfFiles { rank: rank_number, files: pieces.len() });
+    }
+    Ok(Rank::new(pieces))
+}
+
+/// Write a rank in a FEN, where digits are the number of empty squares
+fn rank_to_fen(rank: &Rank) -> String {
+    let mut fen_rank = String::new();
+    let mut n_empty = 0;
+    for file_index in crate::file_index::get_all_file_indices() {
+        match crate::rank::get_piece(rank, &file_index) {
+            Some(piece) => {
+                if n_empty > 0 {
+                    fen_rank.push_str(&n_empty.to_string());
+                    n_empty = 0;
+                }
+                fen_rank.push(get_piece_letter(&piece));
+            },
+            None => n_empty += 1,
+        }
+    }
+    if n_empty > 0 {
+        fen_rank.push_str(&n_empty.to_string());
+    }
+    fen_rank
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starting_position_round_trip() {
+        let position = position_from_fen(STARTING_FEN).unwrap();
+        assert_eq!(position, Position::new());
+        assert_eq!(position_to_fen(&position), STARTING_FEN);
+    }
+    #[test]
+    fn middlegame_round_trip() {
+        let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R b Kq - 12 34";
+        assert_eq!(position_to_fen(&position_from_fen(fen).unwrap()), fen);
+    }
+    #[test]
+    fn clocks_follow_moves() {
+        let mut position = position_from_fen("4k3/8/8/8/8/8/8/4K2R w K - 7 20").unwrap();
+        position.apply_move(&crate::chess_move::Move::new(Square::new("h1"), Square::new("h5")));
+        position.apply_move(&crate::chess_move::Move::new(Square::new("e8"), Square::new("d8")));
+        assert_eq!(position_to_fen(&position), "3k4/8/8/7R/8/8/8/4K3 w - - 9 21");
+    }
+    #[test]
+    fn wrong_number_of_fields() {
+        assert_eq!(position_from_fen("8/8/8/8/8/8/8/8 w - -"), Err(FenError::WrongNumberOfFields(4)));
+        assert_eq!(position_from_fen(""), Err(FenError::WrongNumberOfFields(0)));
+    }
+    #[test]
+    fn wrong_number_of_files() {
+        assert_eq!(board_from_fen("8/8/8/8/8/8/8/7"), Err(FenError::WrongNumberOfFiles { rank: 1, files: 7 }));
+        assert_eq!(board_from_fen("ppppppppp/8/8/8/8/8/8/8"), Err(FenError::WrongNumberOfFiles { rank: 8, files: 9 }));
+    }
+    #[test]
+    fn invalid_piece() {
+        assert_eq!(board_from_fen("8/8/8/8/3x4/8/8/8"), Err(FenError::InvalidPiece { rank: 4, letter: 'x' }));
+        assert_eq!(board_from_fen("8/8/8/8/8/8/8/09"), Err(FenError::InvalidPiece { rank: 1, letter: '0' }));
+    }
+    #[test]
+    fn invalid_fields() {
+        let board = "8/8/8/8/8/8/8/8";
+        assert_eq!(position_from_fen(&format!("{} w KX - 0 1", board)), Err(FenError::InvalidCastlingRights("KX".to_string())));
+        assert_eq!(position_from_fen(&format!("{} w - e4 0 1", board)), Err(FenError::InvalidEnPassant("e4".to_string())));
+        assert_eq!(position_from_fen(&format!("{} w - - -1 1", board)), Err(FenError::InvalidHalfmoveClock("-1".to_string())));
+        assert_eq!(position_from_fen(&format!("{} w - - 0 0", board)), Err(FenError::InvalidFullmoveNumber("0".to_string())));
+    }
+    #[test]
+    fn error_messages() {
+        assert_eq!(FenError::WrongNumberOfRanks(7).to_string(), "A board has 8 ranks separated by '/', found 7");
+        assert_eq!(FenError::WrongNumberOfFiles { rank: 3, files: 9 }.to_string(), "Rank 3 has 9 files, expected 8");
+    }
+}
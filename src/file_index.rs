@@ -26,7 +26,7 @@ impl FileIndex {
     }
 
     pub fn get(&self) -> usize {
-        self.index.clone()
+        self.index
     }
 
 }
@@ -63,6 +63,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn create_file() {
         let file_index = FileIndex::new(3);
         let file_index_again = file_index.clone();
@@ -1,12 +1,16 @@
 use board::get_piece_at_square;
 
 /// A chess game
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq)]
 #[allow(dead_code)]
 pub struct Game {
-    board: crate::board::Board,
-    current_player: crate::color::Color,
+    position: crate::position::Position,
+    rules: crate::rules::Rules,
+    status: crate::game_status::GameStatus,
+    history: Vec<crate::history_entry::HistoryEntry>,
+    undone: Vec<crate::history_entry::HistoryEntry>,
     selector: std::cell::RefCell<crate::selector::Selector>,
+    players: crate::player::Players,
 }
 
 impl Game {
@@ -26,13 +30,59 @@ impl Game {
         selector.set_cursor(Some(crate::square::get_random_square()));
 
         Game {
-            board: crate::board::Board::new(),
-            current_player: crate::color::Color::White,
+            position: crate::position::Position::new(),
+            rules: crate::rules::Rules::new(),
+            status: crate::game_status::GameStatus::Ongoing,
+            history: Vec::new(),
+            undone: Vec::new(),
             selector: std::cell::RefCell::new(selector),
+            players: crate::player::Players::new(),
         }
     }
 
-    pub fn get_board(&self) -> crate::board::Board { self.board.clone() }
+    /// Do a move by the current player, after which it is the other player's turn.
+    /// Returns the piece captured, if any.
+    /// Capturing the king of the other player wins the game.
+    /// The move, with the piece it captured, is added to the history.
+    /// The moves that were undone can no longer be redone
+    /// 
+    /// ```
+    /// use search_and_destroy_chess_2::chess_move::Move;
+    /// use search_and_destroy_chess_2::color::Color;
+    /// use search_and_destroy_chess_2::game::Game;
+    /// use search_and_destroy_chess_2::square::Square;
+    /// 
+    /// let mut game = Game::new();
+    /// let captured = game.do_move(&Move::new(Square::new("e2"), Square::new("e4")));
+    /// assert_eq!(captured, None);
+    /// assert_eq!(game.get_current_player(), Color::Black);
+    /// ```
+    pub fn do_move(&mut self, chess_move: &crate::chess_move::Move) -> Option<crate::piece::Piece> {
+        assert!(!crate::game_status::is_over(self.status), "The game is over");
+        if let Some(piece) = get_piece_at_square(self.position.get_board(), &chess_move.get_from()) {
+            assert!(
+                piece.get_color() == self.get_current_player(),
+                "It is not the turn of {}", crate::color::to_str(piece.get_color())
+            );
+        }
+        self.undone.clear();
+        self.apply_move(chess_move)
+    }
+
+    /// Do a move, add it to the history and update the status,
+    /// without checking if the move can be done
+    fn apply_move(&mut self, chess_move: &crate::chess_move::Move) -> Option<crate::piece::Piece> {
+        let mover = self.get_current_player();
+        let position_before = self.position.clone();
+        let captured = self.position.apply_move(chess_move);
+        let mut done_move = chess_move.clone();
+        done_move.set_captured(captured.clone());
+        self.history.push(crate::history_entry::HistoryEntry::new(done_move, position_before));
+        self.status = calculate_status(&self.position, &self.rules, mover, &captured);
+        captured
+    }
+
+    pub fn get_board(&self) -> crate::board::Board { self.position.get_board().clone() }
 
     /// ```
     /// use search_and_destroy_chess_2::game::Game;
@@ -42,17 +92,317 @@ impl Game {
     /// let current_player = game.get_current_player();
     /// assert_eq!(current_player, Color::White);
     /// ```
-    pub fn get_current_player(&self) -> crate::color::Color { self.current_player.clone() }
+    pub fn get_current_player(&self) -> crate::color::Color { self.position.get_side_to_move() }
+
+    /// Get the moves done so far, from the first to the last
+    /// 
+    /// ```
+    /// use search_and_destroy_chess_2::chess_move::Move;
+    /// use search_and_destroy_chess_2::game::Game;
+    /// use search_and_destroy_chess_2::position::Position;
+    /// use search_and_destroy_chess_2::square::Square;
+    /// 
+    /// let mut game = Game::new();
+    /// assert!(game.get_history().is_empty());
+    /// game.do_move(&Move::new(Square::new("e2"), Square::new("e4")));
+    /// assert_eq!(game.get_history().len(), 1);
+    /// assert_eq!(game.get_history()[0].get_position(), &Position::new());
+    /// ```
+    pub fn get_history(&self) -> &Vec<crate::history_entry::HistoryEntry> { &self.history }
+
+    /// Get what the current player can see, which is all a player gets to choose a move
+    /// 
+    /// ```
+    /// use search_and_destroy_chess_2::color::Color;
+    /// use search_and_destroy_chess_2::game::Game;
+    /// 
+    /// let game = Game::new();
+    /// let observation = game.get_observation();
+    /// assert_eq!(observation.get_color(), Color::White);
+    /// assert_eq!(observation.get_moves().len(), 20);
+    /// ```
+    pub fn get_observation(&self) -> crate::observation::Observation {
+        let color = self.get_current_player();
+        let mut observation = crate::observation::Observation::new(&self.position, &self.rules);
+        observation.set_captured(
+            self.history.iter()
+                .filter(|entry| entry.get_position().get_side_to_move() == color)
+                .filter_map(|entry| entry.get_move().get_captured())
+                .collect()
+        );
+        observation
+    }
+
+    /// Get the computer player of a color, if the color is not played by a human
+    pub fn get_player(&self, color: crate::color::Color) -> Option<&dyn crate::player::Player> { self.players.get(color) }
+
+    /// Get the position, i.e. the board with whose turn it is and the castling rights
+    pub fn get_position(&self) -> &crate::position::Position { &self.position }
+
+    /// Get the rules the game is played by
+    pub fn get_rules(&self) -> crate::rules::Rules { self.rules }
+
+    /// Get the status of the game, e.g. if it is still ongoing or who has won
+    /// 
+    /// ```
+    /// use search_and_destroy_chess_2::game::Game;
+    /// use search_and_destroy_chess_2::game_status::GameStatus;
+    /// 
+    /// let game = Game::new();
+    /// assert_eq!(game.get_status(), GameStatus::Ongoing);
+    /// ```
+    pub fn get_status(&self) -> crate::game_status::GameStatus { self.status }
+
+    pub fn get_selector(&self) -> std::cell::RefMut<'_, crate::selector::Selector> { self.selector.borrow_mut() }
+
+    /// Let a player do a move as the current player,
+    /// given only what the current player can see.
+    /// A move that is blocked by a piece the player cannot see is rejected,
+    /// after which the player chooses again, knowing which moves were rejected.
+    /// Returns if the player did a move.
+    /// A player choosing a move it cannot see to be possible is a bug in that player
+    /// 
+    /// ```
+    /// use search_and_destroy_chess_2::color::Color;
+    /// use search_and_destroy_chess_2::game::Game;
+    /// use search_and_destroy_chess_2::random_player::RandomPlayer;
+    /// 
+    /// let mut game = Game::new();
+    /// assert!(game.play_turn(&mut RandomPlayer::new()));
+    /// assert_eq!(game.get_current_player(), Color::Black);
+    /// ```
+    pub fn play_turn(&mut self, player: &mut dyn crate::player::Player) -> bool {
+        let mut observation = self.get_observation();
+        loop {
+            let chosen = match player.choose_move(&observation) {
+                Some(chosen) => chosen,
+                None => return false,
+            };
+            if self.do_chosen_move(&mut observation, &chosen, &player.get_name()) {
+                return true
+            }
+        }
+    }
+
+    /// Do a move a player chose given an observation of the current position.
+    /// Returns if the move is done. If a piece the player cannot see blocks it,
+    /// the move is rejected in the observation, so the player can choose again.
+    /// A player choosing a move it cannot see to be possible is a bug in that player
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::chess_move::Move;
+    /// use search_and_destroy_chess_2::game::Game;
+    /// use search_and_destroy_chess_2::square::Square;
+    ///
+    /// let mut game = Game::new();
+    /// let mut observation = game.get_observation();
+    /// assert!(game.do_chosen_move(&mut observation, &Move::new(Square::new("e2"), Square::new("e4")), "human"));
+    /// assert!(observation.get_rejected().is_empty());
+    /// ```
+    pub fn do_chosen_move(&mut self, observation: &mut crate::observation::Observation, chosen: &crate::chess_move::Move, player_name: &str) -> bool {
+        assert!(
+            observation.get_moves().iter().any(|chess_move| crate::chess_move::is_same_move(chess_move, chosen)),
+            "Player '{}' chose move '{}', which it cannot see to be possible",
+            player_name, crate::chess_move::to_str(chosen)
+        );
+        if self.try_move(chosen) {
+            return true
+        }
+        observation.reject(chosen);
+        false
+    }
+
+    /// Let the computer player of the current color do a move.
+    /// Returns if a move was done, which is not the case
+    /// if the current color is played by a human or the player does not choose a move
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::color::Color;
+    /// use search_and_destroy_chess_2::game::Game;
+    /// use search_and_destroy_chess_2::random_player::RandomPlayer;
+    ///
+    /// let mut game = Game::new();
+    /// assert!(!game.play_player_turn());
+    /// game.set_player(Color::White, Some(Box::new(RandomPlayer::new())));
+    /// assert!(game.play_player_turn());
+    /// assert_eq!(game.get_current_player(), Color::Black);
+    /// ```
+    pub fn play_player_turn(&mut self) -> bool {
+        let color = self.get_current_player();
+        let mut player = match self.players.take(color) {
+            Some(player) => player,
+            None => return false,
+        };
+        let is_done = self.play_turn(player.as_mut());
+        self.players.set(color, Some(player));
+        is_done
+    }
+
+    /// Redo the last move that was undone.
+    /// Returns if there was a move to redo
+    /// 
+    /// ```
+    /// use search_and_destroy_chess_2::chess_move::Move;
+    /// use search_and_destroy_chess_2::game::Game;
+    /// use search_and_destroy_chess_2::square::Square;
+    /// 
+    /// let mut game = Game::new();
+    /// game.do_move(&Move::new(Square::new("e2"), Square::new("e4")));
+    /// let position_after_move = game.get_position().clone();
+    /// game.undo();
+    /// assert!(game.redo());
+    /// assert_eq!(game.get_position(), &position_after_move);
+    /// assert!(!game.redo());
+    /// ```
+    pub fn redo(&mut self) -> bool {
+        match self.undone.pop() {
+            Some(entry) => {
+                self.apply_move(entry.get_move());
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Set the computer player of a color, where None is for a human
+    pub fn set_player(&mut self, color: crate::color::Color, player: Option<Box<dyn crate::player::Player>>) { self.players.set(color, player) }
 
-    pub fn get_selector(&self) -> std::cell::RefMut<crate::selector::Selector> { self.selector.borrow_mut() }
+    /// Set the position to play from, e.g. one read from a FEN.
+    /// The history is cleared and the game is ongoing
+    /// 
+    /// ```
+    /// use search_and_destroy_chess_2::color::Color;
+    /// use search_and_destroy_chess_2::fen::position_from_fen;
+    /// use search_and_destroy_chess_2::game::Game;
+    /// 
+    /// let mut game = Game::new();
+    /// game.set_position(position_from_fen("4k3/8/8/8/8/8/8/4K3 b - - 0 1").unwrap());
+    /// assert_eq!(game.get_current_player(), Color::Black);
+    /// ```
+    pub fn set_position(&mut self, position: crate::position::Position) {
+        self.position = position;
+        self.history.clear();
+        self.undone.clear();
+        self.status = crate::game_status::GameStatus::Ongoing;
+    }
+
+    /// Set the rules the game is played by
+    /// 
+    /// ```
+    /// use search_and_destroy_chess_2::game::Game;
+    /// use search_and_destroy_chess_2::rules::Rules;
+    /// 
+    /// let mut game = Game::new();
+    /// let mut rules = Rules::new();
+    /// rules.set_castling_through_attack_allowed(false);
+    /// game.set_rules(rules);
+    /// assert!(!game.get_rules().is_castling_through_attack_allowed());
+    /// ```
+    pub fn set_rules(&mut self, rules: crate::rules::Rules) { self.rules = rules }
+
+    /// Try to do a move chosen by the current player from what it can see.
+    /// Returns if the move is done, which it is not
+    /// if a piece the player cannot see blocks it
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::chess_move::Move;
+    /// use search_and_destroy_chess_2::fen::position_from_fen;
+    /// use search_and_destroy_chess_2::game::Game;
+    /// use search_and_destroy_chess_2::square::Square;
+    ///
+    /// // White cannot see the pawn on e5, which blocks the pawn on e4
+    /// let mut game = Game::new();
+    /// game.set_position(position_from_fen("4k3/8/8/4p3/4P3/8/8/4K3 w - - 0 1").unwrap());
+    /// let push = Move::new(Square::new("e4"), Square::new("e5"));
+    /// assert!(game.get_observation().get_moves().contains(&push));
+    /// assert!(!game.try_move(&push));
+    /// assert!(game.try_move(&Move::new(Square::new("e1"), Square::new("d1"))));
+    /// ```
+    pub fn try_move(&mut self, chosen: &crate::chess_move::Move) -> bool {
+        let chess_move = self.position.get_pseudo_legal_moves(&self.rules)
+            .into_iter()
+            .find(|chess_move| crate::chess_move::is_same_move(chess_move, chosen));
+        match chess_move {
+            Some(chess_move) => {
+                self.do_move(&chess_move);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Take the computer player of a color, e.g. to let it think on another thread,
+    /// after which the color has no player
+    pub fn take_player(&mut self, color: crate::color::Color) -> Option<Box<dyn crate::player::Player>> { self.players.take(color) }
+
+    /// Take back the last move, restoring the position before it,
+    /// after which that move can be redone.
+    /// As no move can be done after a game is over, the game is ongoing again.
+    /// Returns if there was a move to undo
+    /// 
+    /// ```
+    /// use search_and_destroy_chess_2::chess_move::Move;
+    /// use search_and_destroy_chess_2::game::Game;
+    /// use search_and_destroy_chess_2::position::Position;
+    /// use search_and_destroy_chess_2::square::Square;
+    /// 
+    /// let mut game = Game::new();
+    /// assert!(!game.undo());
+    /// game.do_move(&Move::new(Square::new("e2"), Square::new("e4")));
+    /// assert!(game.undo());
+    /// assert_eq!(game.get_position(), &Position::new());
+    /// assert!(game.get_history().is_empty());
+    /// ```
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop() {
+            Some(entry) => {
+                self.position = entry.get_position().clone();
+                self.status = crate::game_status::GameStatus::Ongoing;
+                self.undone.push(entry);
+                true
+            },
+            None => false,
+        }
+    }
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Calculate the status of a game after a move by a player.
+/// * if the king of the other player is captured, the player wins
+/// * if the other player cannot do any move, it is a draw
+/// * else, the game is still ongoing
+fn calculate_status(
+    position: &crate::position::Position,
+    rules: &crate::rules::Rules,
+    mover: crate::color::Color,
+    captured: &Option<crate::piece::Piece>
+) -> crate::game_status::GameStatus {
+    if let Some(piece) = captured {
+        if piece.get_type() == crate::piece_type::PieceType::King {
+            return crate::game_status::get_winning_status(mover)
+        }
+    }
+    if position.get_pseudo_legal_moves(rules).is_empty() {
+        return crate::game_status::GameStatus::Draw
+    }
+    crate::game_status::GameStatus::Ongoing
 }
 
 pub fn get_invisible_squares(game: &crate::game::Game, color: crate::color::Color) -> Vec<crate::square::Square> {
     crate::board::get_invisible_squares(&game.get_board(), color)
 }
 
+pub fn get_visible_squares(game: &crate::game::Game, color: crate::color::Color) -> Vec<crate::square::Square> {
+    crate::board::get_visible_squares(&game.get_board(), color)
+}
+
 pub fn get_piece_from_indices(game: &crate::game::Game, file_index: &crate::file_index::FileIndex, rank_index: usize)  -> Option<crate::piece::Piece> {
-    game.get_board().get_piece_from_indices(&file_index, rank_index)
+    game.get_board().get_piece_from_indices(file_index, rank_index)
 }
 
 /// Get the square that is selected to move a piece from, if any
@@ -72,33 +422,106 @@ pub fn get_cursor_to(game: &crate::game::Game) -> Option<crate::square::Square>
 
 /// Get the square that is selected to move a piece to, if any
 pub fn get_piece_at_cursor(game: &crate::game::Game) -> Option<crate::piece::Piece> {
-    let square: Option<crate::square::Square> = get_cursor_square(game);
-    if let None = square {
-        return None
-    }
-    get_piece_at_square(&game.get_board(), &square.unwrap())
+    let square: crate::square::Square = get_cursor_square(game)?;
+    get_piece_at_square(&game.get_board(), &square)
 }
 
-/// Select a square, e.g. by pressing space
-/// * if there is no piece at that square: 
+/// Select a square, e.g. by pressing space.
+/// Nothing happens if the game is over.
+/// * if a promotion is pending: the promotion is cancelled
+///   and the selector is cleared
+/// * if a 'from' is selected and the piece there can move to that square:
+///   that square is selected as 'to'.
+///   If the move is a promotion, the promotion is pending until a piece type is chosen
+///   with `do_promote`. Else the move is done and the selector is cleared
+/// * if there is no piece of the current player at that square: 
 ///   * if no 'from' is selected: nothing happens
 ///   * if a 'from' is selected: the 'from' square is unselected
-/// * if there is a piece at that square:
+/// * if there is a piece of the current player at that square:
 ///   * if no 'from' is selected: that square is selected as 'from'
 ///   * if a 'from' is selected: the new square is selected as 'from'
-pub fn do_select(game: &crate::game::Game) {
+pub fn do_select(game: &mut crate::game::Game) {
     let cursor_square: Option<crate::square::Square> = game.get_selector().get_cursor();
-    if let None = cursor_square {
-        return()
+    if cursor_square.is_none() || crate::game_status::is_over(game.get_status()) {
+        return
     }
-    let piece: Option<crate::piece::Piece> = get_piece_at_cursor(&game);
-    if let None = piece {
-        game.set_from(None);
-        return()
+    if is_promotion_pending(game) {
+        game.get_selector().set_from(None);
+        game.get_selector().set_to(None);
+        return
+    }
+    if let Some(from) = get_cursor_from(game) {
+        let to = cursor_square.unwrap();
+        if is_valid_destination(game, &from, &to) {
+            game.get_selector().set_to(Some(to));
+            let chess_move = crate::chess_move::create_move(&game.get_board(), &from, &to);
+            if crate::position::is_promotion(&game.get_board(), &chess_move) {
+                return
+            }
+            game.do_move(&chess_move);
+            game.get_selector().set_from(None);
+            game.get_selector().set_to(None);
+            return
+        }
+    }
+    let piece: Option<crate::piece::Piece> = get_piece_at_cursor(game)
+        .filter(|piece| piece.get_color() == game.get_current_player());
+    if piece.is_none() {
+        game.get_selector().set_from(None);
+        return
     }
     game.get_selector().set_from(cursor_square);
 }
 
+/// Redo the last move that was undone, e.g. by pressing Ctrl+Y.
+/// Anything selected is unselected
+pub fn do_redo(game: &mut crate::game::Game) {
+    if game.redo() {
+        game.get_selector().set_from(None);
+        game.get_selector().set_to(None);
+    }
+}
+
+/// Take back the last move, e.g. by pressing Ctrl+Z.
+/// Anything selected is unselected
+pub fn do_undo(game: &mut crate::game::Game) {
+    if game.undo() {
+        game.get_selector().set_from(None);
+        game.get_selector().set_to(None);
+    }
+}
+
+/// Promote the pawn of the pending promotion to a piece type,
+/// after which the move is done and the selector is cleared.
+/// Nothing happens if there is no promotion pending
+pub fn do_promote(game: &mut crate::game::Game, piece_type: crate::piece_type::PieceType) {
+    if !is_promotion_pending(game) {
+        return
+    }
+    let from = get_cursor_from(game).unwrap();
+    let to = get_cursor_to(game).unwrap();
+    let mut chess_move = crate::chess_move::create_move(&game.get_board(), &from, &to);
+    chess_move.set_promotion(Some(piece_type));
+    game.do_move(&chess_move);
+    game.get_selector().set_from(None);
+    game.get_selector().set_to(None);
+}
+
+/// Detect if a pawn is selected to move to the last rank,
+/// and the player still has to choose the piece type to promote to.
+/// This is the only time both a 'from' and a 'to' square are selected
+pub fn is_promotion_pending(game: &crate::game::Game) -> bool {
+    get_cursor_from(game).is_some() && get_cursor_to(game).is_some()
+}
+
+/// Detect if the current player can move the piece at the 'from' square to the 'to' square
+fn is_valid_destination(game: &crate::game::Game, from: &crate::square::Square, to: &crate::square::Square) -> bool {
+    game.get_position()
+        .get_pseudo_legal_moves(&game.get_rules())
+        .iter()
+        .any(|chess_move| chess_move.get_from() == *from && chess_move.get_to() == *to)
+}
+
 pub fn move_cursor(game: &crate::game::Game, direction: crate::direction::Direction) {
     game.get_selector().move_cursor(direction);
 }
@@ -139,37 +562,327 @@ mod tests {
     }
     #[test]
     fn select_nothing_at_e4() {
-        let game = Game::new();
+        let mut game = Game::new();
         set_cursor_at(&game, crate::square::Square::new("e4"));
         assert_eq!(get_cursor_square(&game).unwrap(), crate::square::Square::new("e4"));
         assert_eq!(get_cursor_from(&game), None);
         assert_eq!(get_cursor_to(&game), None);
-        do_select(&game);
+        do_select(&mut game);
         assert_eq!(get_cursor_square(&game).unwrap(), crate::square::Square::new("e4"));
         assert_eq!(get_cursor_from(&game), None);
         assert_eq!(get_cursor_to(&game), None);
     }
     #[test]
     fn select_from_piece_at_e2() {
-        let game = Game::new();
+        let mut game = Game::new();
         set_cursor_at(&game, crate::square::Square::new("e2"));
         assert_eq!(get_cursor_square(&game).unwrap(), crate::square::Square::new("e2"));
         assert_eq!(get_cursor_from(&game), None);
         assert_eq!(get_cursor_to(&game), None);
-        do_select(&game);
+        do_select(&mut game);
         assert_eq!(get_cursor_square(&game).unwrap(), crate::square::Square::new("e2"));
         assert_eq!(get_cursor_from(&game).unwrap(), crate::square::Square::new("e2"));
         assert_eq!(get_cursor_to(&game), None);
     }
     #[test]
     fn select_from_piece_then_select_nothing() {
-        let game = Game::new();
+        let mut game = Game::new();
         set_cursor_at(&game, crate::square::Square::new("e2"));
-        do_select(&game);
-        set_cursor_at(&game, crate::square::Square::new("e4"));
-        do_select(&game);
-        assert_eq!(get_cursor_square(&game).unwrap(), crate::square::Square::new("e4"));
+        do_select(&mut game);
+        set_cursor_at(&game, crate::square::Square::new("e5"));
+        do_select(&mut game);
+        assert_eq!(get_cursor_square(&game).unwrap(), crate::square::Square::new("e5"));
         assert_eq!(get_cursor_from(&game), None);
         assert_eq!(get_cursor_to(&game), None);
+        assert_eq!(game.get_board(), crate::board::Board::new());
+        assert_eq!(game.get_current_player(), crate::color::Color::White);
+    }
+    #[test]
+    fn select_from_and_to_does_move() {
+        use crate::board::get_piece_at_square;
+        let mut game = Game::new();
+        set_cursor_at(&game, crate::square::Square::new("g1"));
+        do_select(&mut game);
+        set_cursor_at(&game, crate::square::Square::new("f3"));
+        do_select(&mut game);
+        assert_eq!(get_piece_at_square(&game.get_board(), &crate::square::Square::new("g1")), None);
+        assert_eq!(
+            get_piece_at_square(&game.get_board(), &crate::square::Square::new("f3")),
+            Some(crate::piece::create_white_knight())
+        );
+        assert_eq!(get_cursor_from(&game), None);
+        assert_eq!(get_cursor_to(&game), None);
+        assert_eq!(game.get_current_player(), crate::color::Color::Black);
+    }
+    #[test]
+    fn select_invalid_destination_does_not_move() {
+        let mut game = Game::new();
+        set_cursor_at(&game, crate::square::Square::new("g1"));
+        do_select(&mut game);
+        set_cursor_at(&game, crate::square::Square::new("g3"));
+        do_select(&mut game);
+        assert_eq!(game.get_board(), crate::board::Board::new());
+        assert_eq!(get_cursor_from(&game), None);
+        assert_eq!(game.get_current_player(), crate::color::Color::White);
+    }
+    #[test]
+    fn cannot_select_piece_of_other_player() {
+        let mut game = Game::new();
+        set_cursor_at(&game, crate::square::Square::new("e7"));
+        do_select(&mut game);
+        assert_eq!(get_cursor_from(&game), None);
+    }
+    #[test]
+    fn selecting_piece_of_other_player_unselects_from() {
+        let mut game = Game::new();
+        set_cursor_at(&game, crate::square::Square::new("e2"));
+        do_select(&mut game);
+        set_cursor_at(&game, crate::square::Square::new("e7"));
+        do_select(&mut game);
+        assert_eq!(get_cursor_from(&game), None);
+        assert_eq!(game.get_board(), crate::board::Board::new());
+    }
+    #[test]
+    fn players_take_turns() {
+        let mut game = Game::new();
+        let select_move = |game: &mut Game, from: &str, to: &str| {
+            set_cursor_at(game, crate::square::Square::new(from));
+            do_select(game);
+            set_cursor_at(game, crate::square::Square::new(to));
+            do_select(game);
+        };
+        select_move(&mut game, "e2", "e4");
+        assert_eq!(game.get_current_player(), crate::color::Color::Black);
+        // White cannot move twice
+        select_move(&mut game, "d2", "d4");
+        assert!(crate::board::is_pawn(&game.get_board(), crate::square::Square::new("d2")));
+        assert_eq!(game.get_current_player(), crate::color::Color::Black);
+        select_move(&mut game, "e7", "e5");
+        assert!(crate::board::is_pawn(&game.get_board(), crate::square::Square::new("e5")));
+        assert_eq!(game.get_current_player(), crate::color::Color::White);
+    }
+    #[test]
+    #[should_panic(expected = "It is not the turn of Black")]
+    fn do_move_of_other_player() {
+        let mut game = Game::new();
+        game.do_move(&crate::chess_move::Move::new(crate::square::Square::new("e7"), crate::square::Square::new("e5")));
+    }
+    #[test]
+    fn capture_king_wins() {
+        use crate::chess_move::Move;
+        use crate::game_status::GameStatus;
+        use crate::square::Square;
+        let mut game = Game::new();
+        game.do_move(&Move::new(Square::new("e2"), Square::new("e3")));
+        game.do_move(&Move::new(Square::new("f7"), Square::new("f6")));
+        game.do_move(&Move::new(Square::new("d1"), Square::new("h5")));
+        game.do_move(&Move::new(Square::new("a7"), Square::new("a6")));
+        assert_eq!(game.get_status(), GameStatus::Ongoing);
+        let captured = game.do_move(&Move::new(Square::new("h5"), Square::new("e8")));
+        assert_eq!(captured, Some(crate::piece::create_black_king()));
+        assert_eq!(game.get_status(), GameStatus::WhiteWins);
+
+        // No more moves can be selected
+        set_cursor_at(&game, Square::new("a6"));
+        do_select(&mut game);
+        assert_eq!(get_cursor_from(&game), None);
+    }
+    #[test]
+    #[should_panic(expected = "The game is over")]
+    fn cannot_move_after_game_is_over() {
+        use crate::chess_move::Move;
+        use crate::square::Square;
+        let mut game = Game::new();
+        game.do_move(&Move::new(Square::new("e2"), Square::new("e3")));
+        game.do_move(&Move::new(Square::new("f7"), Square::new("f6")));
+        game.do_move(&Move::new(Square::new("d1"), Square::new("h5")));
+        game.do_move(&Move::new(Square::new("a7"), Square::new("a6")));
+        game.do_move(&Move::new(Square::new("h5"), Square::new("e8")));
+        game.do_move(&Move::new(Square::new("a6"), Square::new("a5")));
+    }
+    #[test]
+    fn no_moves_is_a_draw() {
+        let mut board = crate::board::Board::from_ranks(vec![crate::rank::Rank::new(vec![None; 8]); 8]);
+        board.set_piece_at_square(&crate::square::Square::new("a1"), Some(crate::piece::create_white_king()));
+        board.set_piece_at_square(&crate::square::Square::new("h4"), Some(crate::piece::create_black_pawn()));
+        board.set_piece_at_square(&crate::square::Square::new("h3"), Some(crate::piece::create_white_pawn()));
+        let rules = crate::rules::Rules::new();
+        let castling_rights = crate::castling_rights::CastlingRights::none();
+        let position = crate::position::Position::from_board(board.clone(), crate::color::Color::Black, castling_rights);
+        assert_eq!(
+            calculate_status(&position, &rules, crate::color::Color::White, &None),
+            crate::game_status::GameStatus::Draw
+        );
+        let position = crate::position::Position::from_board(board, crate::color::Color::White, castling_rights);
+        assert_eq!(
+            calculate_status(&position, &rules, crate::color::Color::Black, &None),
+            crate::game_status::GameStatus::Ongoing
+        );
+    }
+    #[test]
+    fn history_records_captures() {
+        use crate::chess_move::Move;
+        use crate::square::Square;
+        let mut game = Game::new();
+        game.do_move(&Move::new(Square::new("e2"), Square::new("e4")));
+        game.do_move(&Move::new(Square::new("d7"), Square::new("d5")));
+        game.do_move(&Move::new(Square::new("e4"), Square::new("d5")));
+        let history = game.get_history();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[1].get_move().get_captured(), None);
+        assert_eq!(history[2].get_move().get_captured(), Some(crate::piece::create_black_pawn()));
+        assert_eq!(history[2].get_position().get_side_to_move(), crate::color::Color::White);
+    }
+    #[test]
+    fn undo_restores_castling_and_en_passant() {
+        use crate::chess_move::Move;
+        use crate::square::Square;
+        let mut game = Game::new();
+        game.set_position(crate::fen::position_from_fen("r3k2r/pppppppp/8/4P3/8/8/PPPP1PPP/R3K2R b KQkq - 0 1").unwrap());
+        game.do_move(&Move::new(Square::new("d7"), Square::new("d5")));
+        let before_en_passant = game.get_position().clone();
+        assert_eq!(game.do_move(&Move::new(Square::new("e5"), Square::new("d6"))), Some(crate::piece::create_black_pawn()));
+        game.do_move(&Move::new(Square::new("e8"), Square::new("g8")));
+        let after_castling = game.get_position().clone();
+        assert!(game.undo());
+        assert_eq!(crate::fen::position_to_fen(game.get_position()), "r3k2r/ppp1pppp/3P4/8/8/8/PPPP1PPP/R3K2R b KQkq - 0 2");
+        assert!(game.undo());
+        assert_eq!(game.get_position(), &before_en_passant);
+        assert_eq!(game.get_position().get_en_passant(), Some(Square::new("d6")));
+        assert!(game.redo());
+        assert!(game.redo());
+        assert_eq!(game.get_position(), &after_castling);
+        assert_eq!(game.get_history().len(), 3);
+    }
+    #[test]
+    fn undo_king_capture() {
+        use crate::chess_move::Move;
+        use crate::square::Square;
+        let mut game = Game::new();
+        for (from, to) in [("e2", "e3"), ("f7", "f6"), ("d1", "h5"), ("a7", "a6"), ("h5", "e8")] {
+            game.do_move(&Move::new(Square::new(from), Square::new(to)));
+        }
+        assert_eq!(game.get_status(), crate::game_status::GameStatus::WhiteWins);
+        do_undo(&mut game);
+        assert_eq!(game.get_status(), crate::game_status::GameStatus::Ongoing);
+        assert_eq!(get_piece_at_square(&game.get_board(), &Square::new("e8")), Some(crate::piece::create_black_king()));
+        do_redo(&mut game);
+        assert_eq!(game.get_status(), crate::game_status::GameStatus::WhiteWins);
+    }
+    #[test]
+    fn new_move_clears_redo() {
+        use crate::chess_move::Move;
+        use crate::square::Square;
+        let mut game = Game::new();
+        game.do_move(&Move::new(Square::new("e2"), Square::new("e4")));
+        game.undo();
+        game.do_move(&Move::new(Square::new("d2"), Square::new("d4")));
+        assert!(!game.redo());
+        assert!(crate::board::is_pawn(&game.get_board(), Square::new("d4")));
+    }
+    #[test]
+    fn select_castling() {
+        use crate::board::get_piece_at_square;
+        use crate::chess_move::Move;
+        use crate::square::Square;
+        let mut game = Game::new();
+        game.do_move(&Move::new(Square::new("g1"), Square::new("f3")));
+        game.do_move(&Move::new(Square::new("a7"), Square::new("a6")));
+        game.do_move(&Move::new(Square::new("e2"), Square::new("e3")));
+        game.do_move(&Move::new(Square::new("a6"), Square::new("a5")));
+        game.do_move(&Move::new(Square::new("f1"), Square::new("e2")));
+        game.do_move(&Move::new(Square::new("a5"), Square::new("a4")));
+        set_cursor_at(&game, Square::new("e1"));
+        do_select(&mut game);
+        set_cursor_at(&game, Square::new("g1"));
+        do_select(&mut game);
+        assert_eq!(get_piece_at_square(&game.get_board(), &Square::new("g1")), Some(crate::piece::create_white_king()));
+        assert_eq!(get_piece_at_square(&game.get_board(), &Square::new("f1")), Some(crate::piece::create_white_rook()));
+    }
+    #[test]
+    fn select_promotion() {
+        use crate::board::get_piece_at_square;
+        use crate::chess_move::Move;
+        use crate::square::Square;
+        let mut game = Game::new();
+        for (from, to) in [("h2", "h4"), ("g7", "g5"), ("h4", "g5"), ("f8", "g7"), ("g5", "g6"), ("g8", "f6"), ("g6", "g7"), ("a7", "a6")] {
+            game.do_move(&Move::new(Square::new(from), Square::new(to)));
+        }
+        do_promote(&mut game, crate::piece_type::PieceType::Queen);
+        assert_eq!(game.get_current_player(), crate::color::Color::White);
+        set_cursor_at(&game, Square::new("g7"));
+        do_select(&mut game);
+        set_cursor_at(&game, Square::new("h8"));
+        do_select(&mut game);
+        assert!(is_promotion_pending(&game));
+        assert_eq!(game.get_current_player(), crate::color::Color::White);
+        do_promote(&mut game, crate::piece_type::PieceType::Rook);
+        assert!(!is_promotion_pending(&game));
+        assert_eq!(get_piece_at_square(&game.get_board(), &Square::new("h8")), Some(crate::piece::create_white_rook()));
+        assert_eq!(game.get_current_player(), crate::color::Color::Black);
+    }
+    #[test]
+    fn cancel_promotion() {
+        use crate::chess_move::Move;
+        use crate::square::Square;
+        let mut game = Game::new();
+        for (from, to) in [("h2", "h4"), ("g7", "g5"), ("h4", "g5"), ("f8", "g7"), ("g5", "g6"), ("g8", "f6"), ("g6", "g7"), ("a7", "a6")] {
+            game.do_move(&Move::new(Square::new(from), Square::new(to)));
+        }
+        set_cursor_at(&game, Square::new("g7"));
+        do_select(&mut game);
+        set_cursor_at(&game, Square::new("g8"));
+        do_select(&mut game);
+        assert!(is_promotion_pending(&game));
+        do_select(&mut game);
+        assert!(!is_promotion_pending(&game));
+        assert_eq!(get_cursor_from(&game), None);
+        assert!(crate::board::is_pawn(&game.get_board(), Square::new("g7")));
+    }
+    #[test]
+    fn select_other_piece_changes_from() {
+        let mut game = Game::new();
+        set_cursor_at(&game, crate::square::Square::new("g1"));
+        do_select(&mut game);
+        set_cursor_at(&game, crate::square::Square::new("e2"));
+        do_select(&mut game);
+        assert_eq!(game.get_board(), crate::board::Board::new());
+        assert_eq!(get_cursor_from(&game).unwrap(), crate::square::Square::new("e2"));
+    }
+    #[test]
+    fn a_blocked_move_is_rejected() {
+        // White tries b2-b4, blocked by a pawn on b4 it cannot see, then tries e1-d1
+        struct TryingPlayer {
+            tried: Vec<crate::chess_move::Move>,
+        }
+        impl crate::player::Player for TryingPlayer {
+            fn choose_move(&mut self, observation: &crate::observation::Observation) -> Option<crate::chess_move::Move> {
+                assert_eq!(observation.get_rejected(), &self.tried);
+                let text = match self.tried.is_empty() { true => "b2b4", false => "e1d1" };
+                let chosen = observation.get_moves().iter().find(|chess_move| crate::chess_move::to_str(chess_move) == text).cloned();
+                self.tried.extend(chosen.clone());
+                chosen
+            }
+            fn get_name(&self) -> String { "trying".to_string() }
+        }
+        let mut game = Game::new();
+        game.set_position(crate::fen::position_from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap());
+        let mut player = TryingPlayer { tried: Vec::new() };
+        assert!(game.play_turn(&mut player));
+        assert_eq!(player.tried.len(), 2);
+        assert_eq!(crate::board::get_piece_at_square(&game.get_board(), &crate::square::Square::new("d1")), Some(crate::piece::create_white_king()));
+    }
+    #[test]
+    fn players_play_against_each_other() {
+        use crate::color::Color;
+        let mut game = Game::new();
+        game.set_player(Color::White, Some(Box::new(crate::random_player::RandomPlayer::from_seed(1))));
+        game.set_player(Color::Black, Some(Box::new(crate::random_player::RandomPlayer::from_seed(2))));
+        while game.get_history().len() < 20 && !crate::game_status::is_over(game.get_status()) {
+            assert!(game.play_player_turn());
+        }
+        assert_eq!(game.get_player(Color::White).unwrap().get_name(), "random");
+        game.set_player(Color::White, None);
+        assert!(game.get_player(Color::White).is_none());
     }
 }
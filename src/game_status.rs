@@ -0,0 +1,86 @@
+/// The status of a game.
+/// In Search And Destroy Chess there is no check:
+/// a player wins by capturing the king of the other player.
+/// ```
+/// use search_and_destroy_chess_2::game_status::GameStatus;
+/// use search_and_destroy_chess_2::game_status::to_str;
+///
+/// assert_eq!(to_str(GameStatus::Ongoing), "Ongoing");
+/// assert_eq!(to_str(GameStatus::WhiteWins), "White wins");
+/// assert_eq!(to_str(GameStatus::BlackWins), "Black wins");
+/// assert_eq!(to_str(GameStatus::Draw), "Draw");
+/// ```
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum GameStatus {
+    Ongoing,
+    WhiteWins,
+    BlackWins,
+    Draw,
+}
+
+/// Get the status in which a color has won
+///
+/// ```
+/// use search_and_destroy_chess_2::color::Color;
+/// use search_and_destroy_chess_2::game_status::GameStatus;
+/// use search_and_destroy_chess_2::game_status::get_winning_status;
+///
+/// assert_eq!(get_winning_status(Color::White), GameStatus::WhiteWins);
+/// assert_eq!(get_winning_status(Color::Black), GameStatus::BlackWins);
+/// ```
+pub fn get_winning_status(color: crate::color::Color) -> GameStatus {
+    match color {
+        crate::color::Color::Black => GameStatus::BlackWins,
+        crate::color::Color::White => GameStatus::WhiteWins,
+    }
+}
+
+/// Detect if a game with this status is over
+///
+/// ```
+/// use search_and_destroy_chess_2::game_status::GameStatus;
+/// use search_and_destroy_chess_2::game_status::is_over;
+///
+/// assert!(!is_over(GameStatus::Ongoing));
+/// assert!(is_over(GameStatus::Draw));
+/// ```
+pub fn is_over(game_status: GameStatus) -> bool {
+    game_status != GameStatus::Ongoing
+}
+
+/// Convert a GameStatus to a String
+///
+/// ```
+/// use search_and_destroy_chess_2::game_status::GameStatus;
+/// use search_and_destroy_chess_2::game_status::to_str;
+///
+/// assert_eq!(to_str(GameStatus::WhiteWins), "White wins");
+/// ```
+pub fn to_str(game_status: GameStatus) -> String {
+    match game_status {
+        GameStatus::Ongoing => "Ongoing".to_string(),
+        GameStatus::WhiteWins => "White wins".to_string(),
+        GameStatus::BlackWins => "Black wins".to_string(),
+        GameStatus::Draw => "Draw".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_over() {
+        assert!(!is_over(GameStatus::Ongoing));
+        assert!(is_over(GameStatus::WhiteWins));
+        assert!(is_over(GameStatus::BlackWins));
+        assert!(is_over(GameStatus::Draw));
+    }
+
+    #[test]
+    fn test_to_str() {
+        assert_eq!(to_str(GameStatus::Ongoing), String::from("Ongoing"));
+        assert_eq!(to_str(GameStatus::BlackWins), String::from("Black wins"));
+        assert_eq!(to_str(GameStatus::Draw), String::from("Draw"));
+    }
+}
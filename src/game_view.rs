@@ -11,6 +11,22 @@ pub struct GameView {
     window: std::cell::RefCell<sfml::graphics::RenderWindow>,
     game: crate::game::Game,
     assets: crate::assets::Assets,
+    is_showing_belief: bool,
+    belief: std::cell::RefCell<Option<(crate::belief::Belief, usize)>>,
+    menu_color: Option<crate::color::Color>,
+    weights: crate::evaluation::Weights,
+    thinking: Option<Thinking>,
+}
+
+/// A computer player choosing a move on another thread,
+/// so that the window keeps responding while it thinks.
+/// The player is given back together with the move it chose
+struct Thinking {
+    color: crate::color::Color,
+    name: String,
+    observation: crate::observation::Observation,
+    receiver: std::sync::mpsc::Receiver<(Box<dyn crate::player::Player>, Option<crate::chess_move::Move>)>,
+    is_cancelled: bool,
 }
 
 impl GameView {
@@ -34,6 +50,11 @@ impl GameView {
             window,
             game: crate::game::Game::new(),
             assets: crate::assets::Assets::new(),
+            is_showing_belief: false,
+            belief: std::cell::RefCell::new(None),
+            menu_color: None,
+            weights: crate::evaluation::Weights::new(),
+            thinking: None,
         }
     }
 
@@ -46,41 +67,163 @@ impl GameView {
         self.draw_square_coordinats();
         self.draw_pieces();
         self.draw_fog_of_war();
+        self.draw_belief();
         self.draw_selector();
+        self.draw_promotion_picker();
+        self.draw_game_status();
+        self.draw_menu();
 
         // Display things on screen
         self.window.borrow_mut().display();
     }
+    /// Draw, if shown, where the viewer believes hidden enemy pieces might be:
+    /// the more pieces might be on a square, the redder it is
+    fn draw_belief(&self) {
+        if !self.is_showing_belief {
+            return
+        }
+        let belief = self.get_belief();
+        let squares = crate::game::get_invisible_squares(&self.game, self.get_viewer());
+        for square in squares {
+            let count = belief.get_possible_count(&square);
+            if count == 0 {
+                continue;
+            }
+            let file_index = crate::square::get_nth_file(&square);
+            let rank_index = crate::square::get_nth_rank(&square);
+            let x = file_index.get() as f32 * get_square_width(self) as f32;
+            // files go up, 'file_index + 1' as tiles are draw from top
+            let y = self.game_height as f32 - ((rank_index + 1) as f32 * get_square_height(self) as f32);
+            let alpha = std::cmp::min(64 + 32 * count, 224) as u8;
+            let mut rectangle = sfml::graphics::RectangleShape::new();
+            use sfml::graphics::Shape;
+            rectangle.set_fill_color(sfml::graphics::Color::rgba(255_u8, 0_u8, 0_u8, alpha));
+            rectangle.set_size(sfml::system::Vector2f::new(get_square_width(self) as f32, get_square_height(self) as f32));
+            rectangle.set_position(sfml::system::Vector2f::new(x, y));
+            self.window.borrow_mut().draw(&rectangle);
+        }
+    }
     /// Draw the question marks ruthlessly obscuring the squares of the board
+    /// the viewer cannot see
     fn draw_fog_of_war(&self) {
-        use crate::color::Color;
         use crate::game::get_invisible_squares;
         use crate::square::get_nth_file;
         use crate::square::get_nth_rank;
 
-        let squares = get_invisible_squares(&self.game, Color::Black);
+        let squares = get_invisible_squares(&self.game, self.get_viewer());
         for square in squares {
             let file_index = get_nth_file(&square);
             let rank_index = get_nth_rank(&square);
-            let x = file_index.get() as f32 * get_square_width(&self) as f32;
+            let x = file_index.get() as f32 * get_square_width(self) as f32;
             // files go up, 'file_index + 1' as tiles are draw from top
-            let y = self.game_height as f32 - ((rank_index + 1) as f32 * get_square_height(&self) as f32);
-            let mut sprite = sfml::graphics::Sprite::with_texture(&self.assets.get_question_mark());
+            let y = self.game_height as f32 - ((rank_index + 1) as f32 * get_square_height(self) as f32);
+            let mut sprite = sfml::graphics::Sprite::with_texture(self.assets.get_question_mark());
             sprite.set_position(sfml::system::Vector2f::new(x, y));
             sprite.set_scale(sfml::system::Vector2f::new(get_scale_x(self), get_scale_y(self)));
             self.window.borrow_mut().draw(&sprite);
         }
     }
-    /// Draw the chess pieces
+    /// Draw the menu to choose the player of a color, if it is shown
+    fn draw_menu(&self) {
+        let color = match self.menu_color {
+            Some(color) => color,
+            None => return,
+        };
+        let name = match &self.thinking {
+            Some(thinking) if thinking.color == color && !thinking.is_cancelled => Some(thinking.name.clone()),
+            _ => self.game.get_player(color).map(|player| player.get_name()),
+        };
+        let mut text = sfml::graphics::Text::new(&crate::ai::get_menu_text(color, name.as_deref()), self.assets.get_font(), 32);
+        text.set_fill_color(sfml::graphics::Color::WHITE);
+        text.set_outline_color(sfml::graphics::Color::BLACK);
+        text.set_outline_thickness(2.0);
+        let bounds = text.local_bounds();
+        let left = (self.game_width as f32 - bounds.width) / 2.0;
+        let top = (self.game_height as f32 - bounds.height) / 2.0;
+
+        let mut background = sfml::graphics::RectangleShape::new();
+        use sfml::graphics::Shape;
+        background.set_fill_color(sfml::graphics::Color::rgba(0, 0, 0, 192));
+        background.set_outline_thickness(2.);
+        background.set_outline_color(sfml::graphics::Color::WHITE);
+        background.set_size(sfml::system::Vector2f::new(bounds.width + 32.0, bounds.height + 32.0));
+        background.set_position(sfml::system::Vector2f::new(left - 16.0, top - 16.0));
+        self.window.borrow_mut().draw(&background);
+
+        text.set_position(sfml::system::Vector2f::new(left, top));
+        self.window.borrow_mut().draw(&text);
+    }
+    /// Draw the result of the game, if the game is over
+    fn draw_game_status(&self) {
+        let status = self.game.get_status();
+        if !crate::game_status::is_over(status) {
+            return
+        }
+        let mut text = sfml::graphics::Text::new(&crate::game_status::to_str(status), self.assets.get_font(), 64);
+        text.set_fill_color(sfml::graphics::Color::RED);
+        text.set_outline_color(sfml::graphics::Color::BLACK);
+        text.set_outline_thickness(2.0);
+        let bounds = text.local_bounds();
+        text.set_position(sfml::system::Vector2f::new(
+            (self.game_width as f32 - bounds.width) / 2.0,
+            (self.game_height as f32 - bounds.height) / 2.0
+        ));
+        self.window.borrow_mut().draw(&text);
+    }
+    /// Draw the pieces a pawn can promote to, if a promotion is pending,
+    /// each with the key to press to choose it
+    fn draw_promotion_picker(&self) {
+        if !crate::game::is_promotion_pending(&self.game) {
+            return
+        }
+        let square_width = get_square_width(self) as f32;
+        let square_height = get_square_height(self) as f32;
+        let piece_types = crate::piece_type::get_promotion_piece_types();
+        let picker_width = piece_types.len() as f32 * square_width;
+        let left = (self.game_width as f32 - picker_width) / 2.0;
+        let top = (self.game_height as f32 - square_height) / 2.0;
+
+        let mut background = sfml::graphics::RectangleShape::new();
+        use sfml::graphics::Shape;
+        background.set_fill_color(sfml::graphics::Color::rgba(0, 0, 0, 192));
+        background.set_outline_thickness(2.);
+        background.set_outline_color(sfml::graphics::Color::WHITE);
+        background.set_size(sfml::system::Vector2f::new(picker_width, square_height));
+        background.set_position(sfml::system::Vector2f::new(left, top));
+        self.window.borrow_mut().draw(&background);
+
+        for (index, piece_type) in piece_types.into_iter().enumerate() {
+            let x = left + index as f32 * square_width;
+            let piece = crate::piece::Piece::new(self.game.get_current_player(), piece_type);
+            let mut sprite = sfml::graphics::Sprite::with_texture(self.assets.get_piece(piece));
+            sprite.set_position(sfml::system::Vector2f::new(x, top));
+            sprite.set_scale(sfml::system::Vector2f::new(get_scale_x(self), get_scale_y(self)));
+            self.window.borrow_mut().draw(&sprite);
+
+            let key = get_promotion_key_name(piece_type);
+            let mut text = sfml::graphics::Text::new(&key, self.assets.get_font(), 32);
+            text.set_position(sfml::system::Vector2f::new(x, top));
+            text.set_scale(sfml::system::Vector2f::new(get_scale_x(self), get_scale_y(self)));
+            self.window.borrow_mut().draw(&text);
+        }
+    }
+    /// Draw the chess pieces the viewer can see
     fn draw_pieces(&self) {
+        let invisible_squares = crate::game::get_invisible_squares(&self.game, self.get_viewer());
         for file_index in crate::file_index::get_all_file_indices() {
             for rank_index in 0..8 {
-                let x = file_index.get() as f32 * get_square_width(&self) as f32;
+                let square = crate::square::Square::new(
+                    &crate::square::create_coordinat_from_indices(&file_index, rank_index as u8)
+                );
+                if invisible_squares.contains(&square) {
+                    continue;
+                }
+                let x = file_index.get() as f32 * get_square_width(self) as f32;
                 // files go up, 'file_index + 1' as tiles are draw from top
-                let y = self.game_height as f32 - ((rank_index + 1) as f32 * get_square_height(&self) as f32);
+                let y = self.game_height as f32 - ((rank_index + 1) as f32 * get_square_height(self) as f32);
                 let piece_option = crate::game::get_piece_from_indices(&self.game, &file_index, rank_index);
                 if let Some(piece) = piece_option {
-                        let mut sprite = sfml::graphics::Sprite::with_texture(&self.assets.get_piece(piece));
+                        let mut sprite = sfml::graphics::Sprite::with_texture(self.assets.get_piece(piece));
                         sprite.set_position(sfml::system::Vector2f::new(x, y));
                         sprite.set_scale(sfml::system::Vector2f::new(get_scale_x(self), get_scale_y(self)));
                         self.window.borrow_mut().draw(&sprite);
@@ -99,16 +242,16 @@ impl GameView {
         if let Some(square) = square_option {
             let file_index = crate::square::get_nth_file(&square);
             let rank_index = crate::square::get_nth_rank(&square);
-            let x = file_index.get() as f32 * get_square_width(&self) as f32;
+            let x = file_index.get() as f32 * get_square_width(self) as f32;
             // files go up, 'file_index + 1' as tiles are draw from top
-            let y = self.game_height as f32 - ((rank_index + 1) as f32 * get_square_height(&self) as f32);
+            let y = self.game_height as f32 - ((rank_index + 1) as f32 * get_square_height(self) as f32);
             let mut rectangle = sfml::graphics::RectangleShape::new();
             use sfml::graphics::Shape;
-            rectangle.set_fill_color(sfml::graphics::Color::rgba(128 as u8, 128 as u8, 128 as u8, 128 as u8));
+            rectangle.set_fill_color(sfml::graphics::Color::rgba(128_u8, 128_u8, 128_u8, 128_u8));
             rectangle.set_origin(sfml::system::Vector2f::new(0 as f32, 0 as f32));
             rectangle.set_outline_thickness(1.);
             rectangle.set_outline_color(sfml::graphics::Color::BLACK);
-            rectangle.set_size(sfml::system::Vector2f::new(get_square_width(&self) as f32, get_square_height(&self) as f32));
+            rectangle.set_size(sfml::system::Vector2f::new(get_square_width(self) as f32, get_square_height(self) as f32));
             rectangle.set_position(sfml::system::Vector2f::new(x, y));
             self.window.borrow_mut().draw(&rectangle);
         }
@@ -119,9 +262,9 @@ impl GameView {
         for square in squares {
             let file_index = crate::square::get_nth_file(&square);
             let rank_index = crate::square::get_nth_rank(&square);
-            let x = file_index.get() as f32 * get_square_width(&self) as f32;
+            let x = file_index.get() as f32 * get_square_width(self) as f32;
             // files go up, 'file_index + 1' as tiles are draw from top
-            let y = self.game_height as f32 - ((rank_index + 1) as f32 * get_square_height(&self) as f32);
+            let y = self.game_height as f32 - ((rank_index + 1) as f32 * get_square_height(self) as f32);
             let coordinat = square.get();
             let mut text = sfml::graphics::Text::new(&coordinat, self.assets.get_font(), 32);
             text.set_position(sfml::system::Vector2f::new(x, y));
@@ -133,11 +276,11 @@ impl GameView {
     fn draw_squares(&self) {
         for file_index in crate::file_index::get_all_file_indices() {
             for rank_index in 0..8 {
-                let x = file_index.get() as f32 * get_square_width(&self) as f32;
+                let x = file_index.get() as f32 * get_square_width(self) as f32;
                 // files go up, 'file_index + 1' as tiles are draw from top
-                let y = self.game_height as f32 - ((rank_index + 1) as f32 * get_square_height(&self) as f32);
+                let y = self.game_height as f32 - ((rank_index + 1) as f32 * get_square_height(self) as f32);
                 let color = crate::board::get_square_color_from_indices(&file_index, rank_index);
-                let mut sprite = sfml::graphics::Sprite::with_texture(&self.assets.get_square(color));
+                let mut sprite = sfml::graphics::Sprite::with_texture(self.assets.get_square(color));
                 sprite.set_position(sfml::system::Vector2f::new(x, y));
                 sprite.set_scale(sfml::system::Vector2f::new(get_scale_x(self), get_scale_y(self)));
                 self.window.borrow_mut().draw(&sprite);
@@ -189,10 +332,76 @@ impl GameView {
         self.game_width
     }
 
+    /// Get the color whose view of the board is shown:
+    /// the color of the human player when playing against the computer,
+    /// else the current player
+    pub fn get_viewer(&self) -> crate::color::Color {
+        use crate::color::Color;
+        match (self.is_computer(Color::White), self.is_computer(Color::Black)) {
+            (false, true) => Color::White,
+            (true, false) => Color::Black,
+            _ => self.game.get_current_player(),
+        }
+    }
+
+    /// Get what the viewer believes about the hidden enemy pieces.
+    /// The belief is kept with the number of moves it was made for,
+    /// and is updated with the moves done since then.
+    /// It is made again from the whole history if the viewer changed,
+    /// or after moves are undone or redone, or another game is set
+    fn get_belief(&self) -> crate::belief::Belief {
+        let viewer = self.get_viewer();
+        let n_moves = self.game.get_history().len();
+        let mut cache = self.belief.borrow_mut();
+        let belief = match cache.take() {
+            Some((mut belief, belief_n_moves)) if belief.get_color() == viewer && belief_n_moves <= n_moves => {
+                crate::belief::update_belief(&mut belief, &self.game, belief_n_moves);
+                belief
+            },
+            _ => crate::belief::get_belief(&self.game, viewer),
+        };
+        *cache = Some((belief.clone(), n_moves));
+        belief
+    }
+
+    /// Detect if a color is played by the computer, which may be thinking
+    fn is_computer(&self, color: crate::color::Color) -> bool {
+        match &self.thinking {
+            Some(thinking) if thinking.color == color && !thinking.is_cancelled => true,
+            _ => self.game.get_player(color).is_some(),
+        }
+    }
+
+    /// Detect if a computer player is choosing a move,
+    /// during which the game cannot be changed
+    fn is_thinking(&self) -> bool {
+        self.thinking.is_some()
+    }
+
+    /// Set the game to show, e.g. one read from a file to replay.
+    /// The players stay the same
+    pub fn set_game(&mut self, mut game: crate::game::Game) {
+        for color in [crate::color::Color::White, crate::color::Color::Black] {
+            game.set_player(color, self.game.take_player(color));
+        }
+        self.game = game;
+        self.belief.replace(None);
+    }
+
+    /// Set the weights the computer players chosen in the menu evaluate positions with
+    pub fn set_weights(&mut self, weights: crate::evaluation::Weights) {
+        self.weights = weights;
+    }
+
+    /// Set the computer player of a color, where None is for a human
+    pub fn set_player(&mut self, color: crate::color::Color, player: Option<Box<dyn crate::player::Player>>) {
+        self.game.set_player(color, player);
+    }
+
     pub fn run(&mut self) {
         self.window.borrow_mut().set_vertical_sync_enabled(true);
 
-        let mut ball_sound: sfml::audio::Sound = sfml::audio::Sound::with_buffer(&self.assets.get_bounce_sound_buffer());
+        let mut ball_sound: sfml::audio::Sound = sfml::audio::Sound::with_buffer(self.assets.get_bounce_sound_buffer());
 
         loop {
             while let Some(event) = &self.window.borrow_mut().poll_event() {
@@ -201,9 +410,53 @@ impl GameView {
                     | sfml::window::Event::KeyPressed {
                         code: sfml::window::Key::ESCAPE, ..
                     } => return,
-                    sfml::window::Event::KeyReleased { code: sfml::window::Key::SPACE, .. } => {
+                    sfml::window::Event::KeyReleased { code: sfml::window::Key::SPACE, .. } if !self.is_thinking() => {
                         ball_sound.play();
-                        crate::game::do_select(&self.game);
+                        crate::game::do_select(&mut self.game);
+                    },
+                    sfml::window::Event::KeyReleased { code: sfml::window::Key::Q, .. } if !self.is_thinking() => {
+                        crate::game::do_promote(&mut self.game, crate::piece_type::PieceType::Queen);
+                    },
+                    sfml::window::Event::KeyReleased { code: sfml::window::Key::R, .. } if !self.is_thinking() => {
+                        crate::game::do_promote(&mut self.game, crate::piece_type::PieceType::Rook);
+                    },
+                    sfml::window::Event::KeyReleased { code: sfml::window::Key::W, .. } if self.menu_color.is_some() => {
+                        self.menu_color = Some(crate::color::Color::White);
+                    },
+                    sfml::window::Event::KeyReleased { code: sfml::window::Key::B, .. } if self.menu_color.is_some() => {
+                        self.menu_color = Some(crate::color::Color::Black);
+                    },
+                    sfml::window::Event::KeyReleased { code: sfml::window::Key::B, .. } if !self.is_thinking() => {
+                        crate::game::do_promote(&mut self.game, crate::piece_type::PieceType::Bishop);
+                    },
+                    sfml::window::Event::KeyReleased { code: sfml::window::Key::N, .. } if !self.is_thinking() => {
+                        crate::game::do_promote(&mut self.game, crate::piece_type::PieceType::Knight);
+                    },
+                    sfml::window::Event::KeyReleased { code: sfml::window::Key::M, .. } => {
+                        self.menu_color = match self.menu_color {
+                            Some(_) => None,
+                            None => Some(crate::color::get_other_color(self.get_viewer())),
+                        };
+                    },
+                    sfml::window::Event::KeyReleased { code, .. } if self.menu_color.is_some() && get_menu_choice(*code).is_some() => {
+                        let color = self.menu_color.unwrap();
+                        let player = get_menu_choice(*code).unwrap().map(|ai| crate::ai::create_player(ai, None, &self.weights));
+                        if let Some(thinking) = self.thinking.as_mut().filter(|thinking| thinking.color == color) {
+                            thinking.is_cancelled = true;
+                        }
+                        self.game.set_player(color, player);
+                        self.menu_color = None;
+                    },
+                    sfml::window::Event::KeyReleased { code: sfml::window::Key::H, .. } => {
+                        self.is_showing_belief = !self.is_showing_belief;
+                    },
+                    sfml::window::Event::KeyPressed { code: sfml::window::Key::Z, ctrl: true, .. } if !self.is_thinking() => {
+                        do_undo_against(&mut self.game);
+                        self.belief.replace(None);
+                    },
+                    sfml::window::Event::KeyPressed { code: sfml::window::Key::Y, ctrl: true, .. } if !self.is_thinking() => {
+                        do_redo_against(&mut self.game);
+                        self.belief.replace(None);
                     },
                     sfml::window::Event::KeyPressed { code: sfml::window::Key::UP, .. } => {
                         crate::game::move_cursor(&self.game, crate::direction::Direction::Up);
@@ -221,26 +474,116 @@ impl GameView {
                 }
             }
 
+            play_computer_turn(&mut self.game, &mut self.thinking);
             self.draw();
         }
     }
 
 }
 
+/// Let the computer player of the current color, if any, choose a move on another thread,
+/// or do the move it chose once it is done.
+/// A move that is rejected makes the player choose again
+fn play_computer_turn(game: &mut crate::game::Game, thinking: &mut Option<Thinking>) {
+    if let Some(current) = thinking {
+        let (player, chosen) = match current.receiver.try_recv() {
+            Ok(result) => result,
+            Err(std::sync::mpsc::TryRecvError::Empty) => return,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => panic!("Player '{}' stopped while choosing a move", current.name),
+        };
+        let mut done = thinking.take().unwrap();
+        if done.is_cancelled {
+            return
+        }
+        game.set_player(done.color, Some(player));
+        if let Some(chosen) = chosen {
+            if !game.do_chosen_move(&mut done.observation, &chosen, &done.name) {
+                *thinking = Some(start_thinking(game, done.observation));
+            }
+        }
+        return
+    }
+    if crate::game_status::is_over(game.get_status()) || game.get_player(game.get_current_player()).is_none() {
+        return
+    }
+    *thinking = Some(start_thinking(game, game.get_observation()));
+}
+
+/// Let the computer player of the current color choose a move on another thread
+fn start_thinking(game: &mut crate::game::Game, observation: crate::observation::Observation) -> Thinking {
+    let color = game.get_current_player();
+    let mut player = game.take_player(color).unwrap();
+    let name = player.get_name();
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let player_observation = observation.clone();
+    std::thread::spawn(move || {
+        let chosen = player.choose_move(&player_observation);
+        // The window may be closed already, so nobody waits for the move
+        let _ = sender.send((player, chosen));
+    });
+    Thinking { color, name, observation, receiver, is_cancelled: false }
+}
+
+/// Detect if the current player is a computer playing against a human,
+/// whose move is undone or redone together with the move of the human
+fn is_computer_against_human(game: &crate::game::Game) -> bool {
+    let color = game.get_current_player();
+    game.get_player(color).is_some() && game.get_player(crate::color::get_other_color(color)).is_none()
+}
+
+/// Get the choice in the menu made by pressing a key, if any:
+/// 0 for a human, else the computer player at that place in the menu
+fn get_menu_choice(code: sfml::window::Key) -> Option<Option<crate::ai::Ai>> {
+    use sfml::window::Key;
+    let keys = [Key::NUM0, Key::NUM1, Key::NUM2, Key::NUM3, Key::NUM4, Key::NUM5, Key::NUM6, Key::NUM7, Key::NUM8, Key::NUM9];
+    let choice = keys.iter().position(|key| *key == code)?;
+    match choice {
+        0 => Some(None),
+        _ => crate::ai::get_all_ais().get(choice - 1).map(|ai| Some(*ai)),
+    }
+}
+
+/// Redo the last move of the human player,
+/// together with the reply of the computer player it plays against, if any
+fn do_redo_against(game: &mut crate::game::Game) {
+    crate::game::do_redo(game);
+    if is_computer_against_human(game) {
+        crate::game::do_redo(game);
+    }
+}
+
+/// Take back the last move of the human player,
+/// together with the reply of the computer player it plays against, if any
+fn do_undo_against(game: &mut crate::game::Game) {
+    crate::game::do_undo(game);
+    if is_computer_against_human(game) {
+        crate::game::do_undo(game);
+    }
+}
+
+/// Get the name of the key to press to promote to a piece type
+pub fn get_promotion_key_name(piece_type: crate::piece_type::PieceType) -> String {
+    match piece_type {
+        crate::piece_type::PieceType::Queen => "Q".to_string(),
+        crate::piece_type::PieceType::Rook => "R".to_string(),
+        crate::piece_type::PieceType::Bishop => "B".to_string(),
+        crate::piece_type::PieceType::Knight => "N".to_string(),
+        _ => panic!("A pawn cannot promote to a {}", crate::piece_type::to_str(piece_type)),
+    }
+}
+
 /// Get the scale factor in the horizontal direction
 pub fn get_scale_x(game_view: &GameView) -> f32{
     let image_width = game_view.get_assets().get_image_width(); // pixels
     let square_width = game_view.get_width() as f32 / 8.0;
-    let scale_x = square_width / image_width as f32;
-    scale_x
+    square_width / image_width as f32
 }
 
 /// Get the scale factor in the vertical direction
 pub fn get_scale_y(game_view: &GameView) -> f32{
     let image_height = game_view.get_assets().get_image_height(); // pixels
     let square_height = game_view.get_height() as f32 / 8.0;
-    let scale_y = square_height / image_height as f32;
-    scale_y
+    square_height / image_height as f32
 }
 
 /// Get the height of a square.
@@ -0,0 +1,289 @@
+use crate::chess_move::Move;
+use crate::color::Color;
+use crate::game::Game;
+use crate::position::Position;
+use crate::rules::Rules;
+use crate::san::SanError;
+
+/// The reasons why a command of the headless game loop cannot be done.
+/// The line number, starting at 1, is given for each error
+///
+/// ```
+/// use search_and_destroy_chess_2::headless::CommandError;
+///
+/// let error = CommandError::UnknownCommand { line: 3, command: "dance".to_string() };
+/// assert_eq!(error.to_string(), "Line 3: Unknown command 'dance'");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CommandError {
+    InvalidFen { line: usize, error: crate::fen::FenError },
+    InvalidMove { line: usize, error: SanError },
+    MoveAfterGameOver { line: usize, text: String },
+    NothingToRedo { line: usize },
+    NothingToUndo { line: usize },
+    UnknownCommand { line: usize, command: String },
+}
+
+impl std::fmt::Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CommandError::InvalidFen { line, error } => write!(f, "Line {}: {}", line, error),
+            CommandError::InvalidMove { line, error } => write!(f, "Line {}: {}", line, error),
+            CommandError::MoveAfterGameOver { line, text } => write!(f, "Line {}: Move '{}' is done after the game is over", line, text),
+            CommandError::NothingToRedo { line } => write!(f, "Line {}: There is no move to redo", line),
+            CommandError::NothingToUndo { line } => write!(f, "Line {}: There is no move to undo", line),
+            CommandError::UnknownCommand { line, command } => write!(f, "Line {}: Unknown command '{}'", line, command),
+        }
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+/// The commands of the headless game loop, besides doing a move
+const HELP: &str = "\
+Commands, one per line:
+  <move>  do a move, in standard algebraic notation (e.g. 'Nf3')
+          or coordinate notation (e.g. 'g1f3', 'a7a8q')
+  board   show the board as seen by the player to move
+  fen     show the position as a FEN, including the hidden pieces
+  help    show this help
+  moves   show the moves the player to move can do
+  pgn     show the game as a PGN
+  position <FEN>
+          start a new game from a position
+  quit    stop
+  redo    redo the last move undone
+  status  show the status of the game
+  undo    take back the last move
+Lines starting with '#' are ignored";
+
+/// Get the move in a position from its coordinate notation (e.g. 'e2e4', 'a7a8q')
+/// or its standard algebraic notation (e.g. 'e4', 'a8=Q')
+///
+/// ```
+/// use search_and_destroy_chess_2::chess_move::Move;
+/// use search_and_destroy_chess_2::headless::get_move_from_text;
+/// use search_and_destroy_chess_2::position::Position;
+/// use search_and_destroy_chess_2::rules::Rules;
+/// use search_and_destroy_chess_2::square::Square;
+///
+/// let position = Position::new();
+/// let expected = Move::new(Square::new("g1"), Square::new("f3"));
+/// assert_eq!(get_move_from_text(&position, &Rules::new(), "g1f3"), Ok(expected.clone()));
+/// assert_eq!(get_move_from_text(&position, &Rules::new(), "Nf3"), Ok(expected));
+/// assert!(get_move_from_text(&position, &Rules::new(), "e2e5").is_err());
+/// ```
+pub fn get_move_from_text(position: &Position, rules: &Rules, text: &str) -> Result<Move, SanError> {
+    let coordinates = text.to_ascii_lowercase();
+    let coordinate_move = position.get_pseudo_legal_moves(rules)
+        .into_iter()
+        .find(|chess_move| crate::chess_move::to_str(chess_move) == coordinates);
+    match coordinate_move {
+        Some(chess_move) => Ok(chess_move),
+        None => crate::san::get_move_from_san(position, rules, text),
+    }
+}
+
+/// Draw the board as text, as seen by a player:
+/// the squares hidden by the fog of war are a '?', empty squares are a '.',
+/// and pieces have their FEN letter. The eighth rank is at the top
+///
+/// ```
+/// use search_and_destroy_chess_2::board::Board;
+/// use search_and_destroy_chess_2::color::Color;
+/// use search_and_destroy_chess_2::headless::get_board_text;
+///
+/// let text = get_board_text(&Board::new(), Color::White);
+/// assert!(text.starts_with("8 ????????\n"));
+/// assert!(text.contains("\n1 RNBQKBNR\n"));
+/// assert!(text.ends_with("  abcdefgh\n"));
+/// ```
+pub fn get_board_text(board: &crate::board::Board, color: Color) -> String {
+    let visible = crate::board::get_visible_bitboard(board, color);
+    let mut text = String::new();
+    for rank_index in (0..8).rev() {
+        text.push_str(&format!("{} ", rank_index + 1));
+        for file_index in 0..8 {
+            let square = crate::square::Square::from_indices(file_index, rank_index);
+            let letter = match (visible.contains(&square), crate::board::get_piece_at_square(board, &square)) {
+                (false, _) => '?',
+                (true, Some(piece)) => crate::fen::get_piece_letter(&piece),
+                (true, None) => '.',
+            };
+            text.push(letter);
+        }
+        text.push('\n');
+    }
+    text.push_str("  abcdefgh\n");
+    text
+}
+
+/// Play a game without a window, by reading one command per line from the input
+/// and writing the replies to the output, until the input ends or 'quit' is read.
+/// A command that cannot be done is reported on the output and skipped.
+/// Returns the commands that could not be done
+///
+/// ```
+/// use search_and_destroy_chess_2::game::Game;
+/// use search_and_destroy_chess_2::game_status::GameStatus;
+/// use search_and_destroy_chess_2::headless::run_headless;
+///
+/// let mut game = Game::new();
+/// let mut output: Vec<u8> = Vec::new();
+/// let errors = run_headless(&mut game, "e4\ne7e5\nKe3\n".as_bytes(), &mut output).unwrap();
+/// assert_eq!(game.get_history().len(), 2);
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(game.get_status(), GameStatus::Ongoing);
+/// ```
+pub fn run_headless<R: std::io::BufRead, W: std::io::Write>(
+    game: &mut Game,
+    input: R,
+    output: &mut W
+) -> std::io::Result<Vec<CommandError>> {
+    let mut errors: Vec<CommandError> = Vec::new();
+    for (index, line) in input.lines().enumerate() {
+        let line = line?;
+        let command = line.trim();
+        if command.is_empty() || command.starts_with('#') {
+            continue;
+        }
+        if command == "quit" {
+            break;
+        }
+        match do_command(game, command, index + 1) {
+            Ok(reply) => writeln!(output, "{}", reply)?,
+            Err(error) => {
+                writeln!(output, "Error: {}", error)?;
+                errors.push(error);
+            },
+        }
+    }
+    Ok(errors)
+}
+
+/// Do one command of the headless game loop, returning the reply
+fn do_command(game: &mut Game, command: &str, line: usize) -> Result<String, CommandError> {
+    match command {
+        "board" => Ok(get_board_text(game.get_position().get_board(), game.get_current_player()).trim_end().to_string()),
+        "fen" => Ok(crate::fen::position_to_fen(game.get_position())),
+        "help" => Ok(HELP.to_string()),
+        "moves" => {
+            let position = game.get_position();
+            let sans: Vec<String> = position.get_pseudo_legal_moves(&game.get_rules())
+                .iter()
+                .map(|chess_move| crate::san::get_san(position, chess_move))
+                .collect();
+            Ok(sans.join(" "))
+        },
+        "pgn" => Ok(crate::pgn::to_pgn(game, false).trim_end().to_string()),
+        "redo" => match game.redo() {
+            true => Ok(get_status_text(game)),
+            false => Err(CommandError::NothingToRedo { line }),
+        },
+        "status" => Ok(get_status_text(game)),
+        "undo" => match game.undo() {
+            true => Ok(get_status_text(game)),
+            false => Err(CommandError::NothingToUndo { line }),
+        },
+        _ if command.starts_with("position ") => {
+            let fen = command["position ".len()..].trim();
+            let position = crate::fen::position_from_fen(fen)
+                .map_err(|error| CommandError::InvalidFen { line, error })?;
+            game.set_position(position);
+            Ok(get_status_text(game))
+        },
+        _ => do_move_command(game, command, line),
+    }
+}
+
+/// Do a move given as text, returning its SAN and the status after it,
+/// e.g. 'Qxe8: White wins'
+fn do_move_command(game: &mut Game, text: &str, line: usize) -> Result<String, CommandError> {
+    let first = text.chars().next().unwrap_or(' ');
+    if !first.is_ascii_alphanumeric() || text.contains(char::is_whitespace) {
+        return Err(CommandError::UnknownCommand { line, command: text.to_string() })
+    }
+    if crate::game_status::is_over(game.get_status()) {
+        return Err(CommandError::MoveAfterGameOver { line, text: text.to_string() })
+    }
+    let chess_move = get_move_from_text(game.get_position(), &game.get_rules(), text)
+        .map_err(|error| match error {
+            SanError::Malformed(_) => CommandError::UnknownCommand { line, command: text.to_string() },
+            _ => CommandError::InvalidMove { line, error },
+        })?;
+    let san = crate::san::get_san(game.get_position(), &chess_move);
+    game.do_move(&chess_move);
+    Ok(format!("{}: {}", san, get_status_text(game)))
+}
+
+/// Describe the status of a game, e.g. 'White to move' or 'Black wins'
+fn get_status_text(game: &Game) -> String {
+    match game.get_status() {
+        crate::game_status::GameStatus::Ongoing => format!("{} to move", crate::color::to_str(game.get_current_player())),
+        status => crate::game_status::to_str(status),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run the headless game loop from the start position,
+    /// returning the game, the output and the errors
+    fn run(input: &str) -> (Game, String, Vec<CommandError>) {
+        let mut game = Game::new();
+        let mut output: Vec<u8> = Vec::new();
+        let errors = run_headless(&mut game, input.as_bytes(), &mut output).unwrap();
+        (game, String::from_utf8(output).unwrap(), errors)
+    }
+
+    #[test]
+    fn play_until_king_capture() {
+        let (game, output, errors) = run("e4\nf5\nQh5\ng6\nQxg6\nh6\nQxe8\n");
+        assert!(errors.is_empty());
+        assert_eq!(game.get_status(), crate::game_status::GameStatus::WhiteWins);
+        assert!(output.ends_with("Qxe8: White wins\n"));
+    }
+    #[test]
+    fn move_after_game_over() {
+        let (_, _, errors) = run("e4\nf5\nQh5\ng6\nQxg6\nh6\nQxe8\na6\n");
+        assert_eq!(errors, vec![CommandError::MoveAfterGameOver { line: 8, text: "a6".to_string() }]);
+    }
+    #[test]
+    fn comments_and_quit() {
+        let (game, _, errors) = run("# Opening\n\ne4\nquit\ne5\n");
+        assert!(errors.is_empty());
+        assert_eq!(game.get_history().len(), 1);
+    }
+    #[test]
+    fn undo_and_redo() {
+        let (game, output, errors) = run("undo\ne4\nundo\nredo\nredo\n");
+        assert_eq!(errors, vec![CommandError::NothingToUndo { line: 1 }, CommandError::NothingToRedo { line: 5 }]);
+        assert_eq!(game.get_history().len(), 1);
+        assert!(output.contains("White to move\nBlack to move\n"));
+    }
+    #[test]
+    fn unknown_command() {
+        let (_, output, errors) = run("dance\n");
+        assert_eq!(errors, vec![CommandError::UnknownCommand { line: 1, command: "dance".to_string() }]);
+        assert_eq!(output, "Error: Line 1: Unknown command 'dance'\n");
+    }
+    #[test]
+    fn board_is_fogged() {
+        let (_, output, _) = run("e4\nboard\n");
+        assert!(output.contains("8 rnbqkbnr\n7 pppppppp\n6 ........\n5 ........\n4 ????????\n"));
+    }
+    #[test]
+    fn promotion_in_coordinate_notation() {
+        let (_, output, errors) = run("position 4k3/P7/8/8/8/8/8/4K3 w - - 0 1\na7a8n\n");
+        assert!(errors.is_empty());
+        assert_eq!(output, "White to move\na8=N: Black to move\n");
+    }
+    #[test]
+    fn invalid_position() {
+        let (game, _, errors) = run("e4\nposition 8/8 w - - 0 1\n");
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], CommandError::InvalidFen { line: 2, .. }));
+        assert_eq!(game.get_history().len(), 1);
+    }
+}
@@ -0,0 +1,40 @@
+use crate::chess_move::Move;
+use crate::position::Position;
+
+/// A move done in a game, together with the position it was done in.
+/// The position before the move is what is needed to write the move down,
+/// to know what the player could see, and to take the move back
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct HistoryEntry {
+    chess_move: Move,
+    position: Position,
+}
+
+impl HistoryEntry {
+
+    /// Create a history entry from a move and the position before that move
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::chess_move::Move;
+    /// use search_and_destroy_chess_2::history_entry::HistoryEntry;
+    /// use search_and_destroy_chess_2::position::Position;
+    /// use search_and_destroy_chess_2::square::Square;
+    ///
+    /// let chess_move = Move::new(Square::new("e2"), Square::new("e4"));
+    /// let entry = HistoryEntry::new(chess_move.clone(), Position::new());
+    /// assert_eq!(entry.get_move(), &chess_move);
+    /// assert_eq!(entry.get_position(), &Position::new());
+    /// ```
+    pub fn new(chess_move: Move, position: Position) -> HistoryEntry {
+        HistoryEntry {
+            chess_move,
+            position,
+        }
+    }
+
+    /// Get the move done
+    pub fn get_move(&self) -> &Move { &self.chess_move }
+
+    /// Get the position before the move was done
+    pub fn get_position(&self) -> &Position { &self.position }
+}
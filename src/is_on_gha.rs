@@ -13,6 +13,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::bool_comparison)]
     fn test_is_on_gha() {
         assert!(is_on_gha() == true || is_on_gha() == false);
     }
@@ -0,0 +1,269 @@
+use crate::belief::Belief;
+use crate::chess_move::Move;
+use crate::color::Color;
+use crate::observation::Observation;
+use crate::piece_type::PieceType;
+use crate::position::Position;
+use crate::rules::Rules;
+use rand::SeedableRng;
+use rand::seq::SliceRandom;
+
+/// A node in the search tree: a move, how often it was chosen,
+/// how often it could have been chosen, and the total reward
+/// for the player that did the move
+struct Node {
+    chess_move: Option<Move>,
+    visits: u32,
+    availability: u32,
+    reward: f64,
+    children: Vec<Node>,
+}
+
+impl Node {
+    fn new(chess_move: Option<Move>) -> Node {
+        Node {
+            chess_move,
+            visits: 0,
+            availability: 0,
+            reward: 0.0,
+            children: Vec::new(),
+        }
+    }
+
+    /// Get the upper confidence bound of a child, used to select it
+    fn get_upper_confidence_bound(&self, exploration: f64) -> f64 {
+        let mean = self.reward / self.visits as f64;
+        mean + exploration * ((self.availability as f64).ln() / self.visits as f64).sqrt()
+    }
+}
+
+/// A player using information-set Monte Carlo tree search (ISMCTS).
+/// As the enemy pieces hidden by the fog of war are unknown,
+/// each iteration samples a position that is possible given what the player has seen,
+/// drawing the hidden pieces from where the player believes they might be,
+/// and searches one path of a single tree shared by all sampled positions.
+/// The move chosen is the one searched most often.
+///
+/// The search stops after a number of iterations,
+/// or earlier when there is a time limit and it is reached
+pub struct IsmctsPlayer {
+    iterations: usize,
+    time_limit: Option<std::time::Duration>,
+    exploration: f64,
+    playout_depth: usize,
+    rng: rand::rngs::StdRng,
+    belief: Option<Belief>,
+}
+
+impl IsmctsPlayer {
+
+    /// Create a player that does a number of iterations per move
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::ismcts::IsmctsPlayer;
+    ///
+    /// let player = IsmctsPlayer::new(1000);
+    /// assert_eq!(player.get_iterations(), 1000);
+    /// assert_eq!(player.get_time_limit(), None);
+    /// ```
+    pub fn new(iterations: usize) -> IsmctsPlayer {
+        IsmctsPlayer {
+            iterations,
+            time_limit: None,
+            exploration: 0.7,
+            playout_depth: 40,
+            rng: rand::rngs::StdRng::from_entropy(),
+            belief: None,
+        }
+    }
+
+    /// Create a player that does a number of iterations per move,
+    /// and searches the same way for the same seed
+    pub fn from_seed(iterations: usize, seed: u64) -> IsmctsPlayer {
+        let mut player = IsmctsPlayer::new(iterations);
+        player.rng = rand::rngs::StdRng::seed_from_u64(seed);
+        player
+    }
+
+    /// Get how much unexplored moves are preferred over moves that did well
+    pub fn get_exploration(&self) -> f64 { self.exploration }
+
+    /// Get the maximum number of iterations per move
+    pub fn get_iterations(&self) -> usize { self.iterations }
+
+    /// Get the maximum number of plies of a random playout,
+    /// after which the game counts as a draw
+    pub fn get_playout_depth(&self) -> usize { self.playout_depth }
+
+    /// Get the maximum time to search per move, if any
+    pub fn get_time_limit(&self) -> Option<std::time::Duration> { self.time_limit }
+
+    /// Set how much unexplored moves are preferred over moves that did well
+    pub fn set_exploration(&mut self, exploration: f64) { self.exploration = exploration }
+
+    /// Set the maximum number of iterations per move
+    pub fn set_iterations(&mut self, iterations: usize) { self.iterations = iterations }
+
+    /// Set the maximum number of plies of a random playout
+    pub fn set_playout_depth(&mut self, playout_depth: usize) { self.playout_depth = playout_depth }
+
+    /// Set the maximum time to search per move, if any
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::ismcts::IsmctsPlayer;
+    /// use std::time::Duration;
+    ///
+    /// let mut player = IsmctsPlayer::new(usize::MAX);
+    /// player.set_time_limit(Some(Duration::from_millis(100)));
+    /// assert_eq!(player.get_time_limit(), Some(Duration::from_millis(100)));
+    /// ```
+    pub fn set_time_limit(&mut self, time_limit: Option<std::time::Duration>) { self.time_limit = time_limit }
+
+    /// Search one path of the tree in a sampled position, expanding it by one node
+    /// and finishing the game by random moves.
+    /// Returns the reward for White: 1 if White wins, 0 if Black wins, else 0.5
+    fn iterate(&mut self, node: &mut Node, position: &mut Position, rules: &Rules) -> f64 {
+        let moves = position.get_pseudo_legal_moves(rules);
+        if moves.is_empty() {
+            return 0.5
+        }
+        for child in node.children.iter_mut() {
+            if moves.iter().any(|chess_move| is_same(child, chess_move)) {
+                child.availability += 1;
+            }
+        }
+        let untried: Vec<&Move> = moves.iter()
+            .filter(|chess_move| !node.children.iter().any(|child| is_same(child, chess_move)))
+            .collect();
+        let mover = position.get_side_to_move();
+        let (child, reward) = match untried.choose(&mut self.rng) {
+            Some(chess_move) => {
+                let mut child = Node::new(Some((*chess_move).clone()));
+                child.availability = 1;
+                let reward = match do_move(position, chess_move) {
+                    Some(reward) => reward,
+                    None => self.playout(position, rules),
+                };
+                node.children.push(child);
+                (node.children.last_mut().unwrap(), reward)
+            },
+            None => {
+                let exploration = self.exploration;
+                let child = node.children.iter_mut()
+                    .filter(|child| moves.iter().any(|chess_move| is_same(child, chess_move)))
+                    .max_by(|a, b| {
+                        a.get_upper_confidence_bound(exploration)
+                            .total_cmp(&b.get_upper_confidence_bound(exploration))
+                    })
+                    .unwrap();
+                let chess_move = moves.iter().find(|chess_move| is_same(child, chess_move)).unwrap();
+                let reward = match do_move(position, chess_move) {
+                    Some(reward) => reward,
+                    None => self.iterate(child, position, rules),
+                };
+                (child, reward)
+            },
+        };
+        child.visits += 1;
+        child.reward += get_reward_for(reward, mover);
+        reward
+    }
+
+    /// Do random moves until the game is over or the playout depth is reached.
+    /// Returns the reward for White
+    fn playout(&mut self, position: &mut Position, rules: &Rules) -> f64 {
+        for _ in 0..self.playout_depth {
+            let moves = position.get_pseudo_legal_moves(rules);
+            let chess_move = match moves.choose(&mut self.rng) {
+                Some(chess_move) => chess_move,
+                None => return 0.5,
+            };
+            if let Some(reward) = do_move(position, chess_move) {
+                return reward
+            }
+        }
+        0.5
+    }
+
+}
+
+impl crate::player::Player for IsmctsPlayer {
+    fn choose_move(&mut self, observation: &Observation) -> Option<Move> {
+        if observation.get_moves().len() <= 1 {
+            let chosen = observation.get_moves().first().cloned();
+            if let Some(chess_move) = &chosen {
+                let mut belief = crate::belief::get_updated_belief(self.belief.take(), observation);
+                belief.do_own_move(observation, chess_move);
+                self.belief = Some(belief);
+            }
+            return chosen
+        }
+        let start = std::time::Instant::now();
+        let rules = observation.get_rules();
+        let mut belief = crate::belief::get_updated_belief(self.belief.take(), observation);
+        let mut root = Node::new(None);
+        for _ in 0..self.iterations {
+            if self.time_limit.is_some_and(|time_limit| start.elapsed() >= time_limit) {
+                break;
+            }
+            let mut position = crate::determinization::sample_position_from_belief(observation, &belief, &mut self.rng);
+            self.iterate(&mut root, &mut position, &rules);
+        }
+        let best = root.children.iter().max_by_key(|child| child.visits);
+        let chosen = match best {
+            Some(child) => observation.get_moves().iter().find(|chess_move| is_same(child, chess_move)).cloned(),
+            None => observation.get_moves().choose(&mut self.rng).cloned(),
+        };
+        if let Some(chess_move) = &chosen {
+            belief.do_own_move(observation, chess_move);
+        }
+        self.belief = Some(belief);
+        chosen
+    }
+
+    fn get_name(&self) -> String { "ismcts".to_string() }
+}
+
+/// Do a move in a position.
+/// Returns the reward for White if the move captures a king, which ends the game
+fn do_move(position: &mut Position, chess_move: &Move) -> Option<f64> {
+    let mover = position.get_side_to_move();
+    match position.apply_move(chess_move) {
+        Some(piece) if piece.get_type() == PieceType::King => Some(get_reward_for(1.0, mover)),
+        _ => None,
+    }
+}
+
+/// Convert a reward for White to the reward for a color
+fn get_reward_for(reward: f64, color: Color) -> f64 {
+    match color {
+        Color::White => reward,
+        Color::Black => 1.0 - reward,
+    }
+}
+
+/// Detect if a node is for a move
+fn is_same(node: &Node, chess_move: &Move) -> bool {
+    node.chess_move.as_ref().is_some_and(|node_move| crate::chess_move::is_same_move(node_move, chess_move))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::Player;
+
+    #[test]
+    fn chooses_a_move_it_can_do() {
+        let game = crate::save::game_from_save("e2e4\nd7d5\n").unwrap();
+        let observation = game.get_observation();
+        let chess_move = IsmctsPlayer::from_seed(100, 7).choose_move(&observation).unwrap();
+        assert!(observation.get_moves().contains(&chess_move));
+    }
+    #[test]
+    fn keeps_its_belief_during_a_game() {
+        let mut white = IsmctsPlayer::from_seed(20, 3);
+        let mut black = crate::random_player::RandomPlayer::from_seed(4);
+        crate::player::play_game(&mut white, &mut black, 20);
+        assert!(white.belief.is_some());
+    }
+}
@@ -1,13 +1,40 @@
+pub mod ai;
+pub mod alpha_beta;
 pub mod assets;
+pub mod belief;
+pub mod bitboard;
 pub mod board;
+pub mod castling_rights;
+pub mod chess_move;
+pub mod cli;
 pub mod color;
+pub mod convert;
+pub mod determinization;
 pub mod direction;
+pub mod evaluation;
+pub mod fen;
 pub mod file_index;
 pub mod game;
+pub mod game_status;
+pub mod headless;
+pub mod history_entry;
 pub mod is_on_gha;
+pub mod ismcts;
+pub mod move_generator;
+pub mod observation;
+pub mod perft;
+pub mod pgn;
 pub mod piece;
 pub mod piece_type;
+pub mod player;
+pub mod position;
+pub mod random_player;
 pub mod rank;
 pub mod rank_index;
+pub mod rules;
+pub mod san;
+pub mod save;
 pub mod selector;
 pub mod square;
+
+extern crate rand;
@@ -1,15 +1,40 @@
+pub mod ai;
+pub mod alpha_beta;
 pub mod assets;
+pub mod belief;
+pub mod bitboard;
 pub mod board;
+pub mod castling_rights;
+pub mod chess_move;
+pub mod cli;
 pub mod color;
+pub mod convert;
+pub mod determinization;
 pub mod direction;
+pub mod evaluation;
+pub mod fen;
 pub mod file_index;
 pub mod game;
+pub mod game_status;
 pub mod game_view;
+pub mod headless;
+pub mod history_entry;
 pub mod is_on_gha;
+pub mod ismcts;
+pub mod move_generator;
+pub mod observation;
+pub mod perft;
+pub mod pgn;
 pub mod piece;
 pub mod piece_type;
+pub mod player;
+pub mod position;
+pub mod random_player;
 pub mod rank;
 pub mod rank_index;
+pub mod rules;
+pub mod san;
+pub mod save;
 pub mod selector;
 pub mod square;
 pub mod scribble;
@@ -18,14 +43,208 @@ extern crate rand;
 extern crate sfml;
 
 fn main() {
-    if crate::is_on_gha::is_on_gha() {
-        println!("On GHA");
+    use crate::cli::Command;
 
-    } else {
-        use crate::game_view::GameView;
-        let game_width = 800;
-        let game_height = 600;
-        let mut game_view = GameView::new(game_width, game_height);
-        game_view.run()
+    let arguments: Vec<String> = std::env::args().skip(1).collect();
+    let command = match crate::cli::parse_arguments(&arguments) {
+        Ok(command) => command,
+        Err(error) => {
+            eprintln!("Error: {}\n\n{}", error, crate::cli::USAGE);
+            std::process::exit(2);
+        },
+    };
+    let result = match command {
+        Command::Convert { input, from, to, output } => run_convert(&input, from, to, output),
+        Command::Evaluate { fen, color, weights } => run_evaluate(&fen, color, weights),
+        Command::Headless { move_file } => run_headless(move_file),
+        Command::Help => {
+            println!("{}", crate::cli::USAGE);
+            Ok(())
+        },
+        Command::Perft { fen, depth, divide, king_capture } => run_perft(&fen, depth, divide, king_capture),
+        // There is no display on GitHub Actions, so play without a window there
+        Command::Play { .. } if crate::is_on_gha::is_on_gha() => run_headless(None),
+        Command::Play { width, height, white, black, weights } => run_play(width, height, white, black, weights),
+        Command::Replay { filename, ply, headless } => run_replay(&filename, ply, headless),
+        Command::Selfplay { games, white, black, max_plies, seed, output, weights } => {
+            run_selfplay(games, white, black, max_plies, seed, output, weights)
+        },
+    };
+    if let Err(error) = result {
+        eprintln!("Error: {}", error);
+        std::process::exit(1);
     }
-}
\ No newline at end of file
+}
+
+/// Convert a game or position from one format to another,
+/// writing it to a file, or to stdout if there is none
+fn run_convert(
+    input: &str,
+    from: Option<crate::convert::Format>,
+    to: crate::convert::Format,
+    output: Option<String>
+) -> Result<(), Box<dyn std::error::Error>> {
+    let text = std::fs::read_to_string(input)?;
+    let from = from.unwrap_or_else(|| crate::convert::get_format_from_filename(input));
+    let mut converted = crate::convert::convert(&text, from, to)?;
+    if !converted.ends_with('\n') {
+        converted.push('\n');
+    }
+    write_output(&converted, output)
+}
+
+/// Show the parts of the evaluation of a position for a color,
+/// or for the player to move if there is none
+fn run_evaluate(fen: &str, color: Option<crate::color::Color>, weights: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+    let position = crate::fen::position_from_fen(fen)?;
+    let color = color.unwrap_or(position.get_side_to_move());
+    let weights = read_weights(weights)?;
+    let evaluation = crate::evaluation::get_evaluation(position.get_board(), color, &weights);
+    println!("Material: {}", evaluation.get_material());
+    println!("Piece-square: {}", evaluation.get_piece_square());
+    println!("King safety: {}", evaluation.get_king_safety());
+    println!("Mobility: {}", evaluation.get_mobility());
+    println!("Total for {}: {}", crate::color::to_str(color), evaluation.get_total());
+    Ok(())
+}
+
+/// Play a game without a window, reading the commands from a move file,
+/// or from stdin if there is none.
+/// It is an error if a command cannot be done
+fn run_headless(move_file: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut game = crate::game::Game::new();
+    let stdout = std::io::stdout();
+    let mut output = stdout.lock();
+    let errors = match move_file {
+        Some(filename) => {
+            let file = std::fs::File::open(filename)?;
+            crate::headless::run_headless(&mut game, std::io::BufReader::new(file), &mut output)?
+        },
+        None => crate::headless::run_headless(&mut game, std::io::stdin().lock(), &mut output)?,
+    };
+    match errors.len() {
+        0 => Ok(()),
+        n_errors => Err(format!("{} command(s) could not be done", n_errors).into()),
+    }
+}
+
+/// Show the perft count of a position, or the count after each move
+fn run_perft(fen: &str, depth: usize, divide: bool, king_capture: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let position = crate::fen::position_from_fen(fen)?;
+    let rules = crate::rules::Rules::new();
+    match (divide, king_capture) {
+        (true, true) => print!("{}", crate::perft::format_divide(&crate::perft::divide_king_capture(&position, &rules, depth))),
+        (true, false) => print!("{}", crate::perft::format_divide(&crate::perft::divide(&position, depth))),
+        (false, true) => println!("{}", crate::perft::perft_king_capture(&position, &rules, depth)),
+        (false, false) => println!("{}", crate::perft::perft(&position, depth)),
+    }
+    Ok(())
+}
+
+/// Read the evaluation weights from a file, or use the default weights if there is none
+fn read_weights(filename: Option<String>) -> Result<crate::evaluation::Weights, Box<dyn std::error::Error>> {
+    match filename {
+        Some(filename) => Ok(crate::evaluation::weights_from_str(&std::fs::read_to_string(filename)?)?),
+        None => Ok(crate::evaluation::Weights::new()),
+    }
+}
+
+/// Play in a window, where the computer plays the colors that have an AI,
+/// evaluating positions with the weights read from a file, if any
+fn run_play(
+    width: u32,
+    height: u32,
+    white: Option<crate::ai::Ai>,
+    black: Option<crate::ai::Ai>,
+    weights: Option<String>
+) -> Result<(), Box<dyn std::error::Error>> {
+    let weights = read_weights(weights)?;
+    let mut game_view = crate::game_view::GameView::new(width, height);
+    game_view.set_player(crate::color::Color::White, white.map(|ai| crate::ai::create_player(ai, None, &weights)));
+    game_view.set_player(crate::color::Color::Black, black.map(|ai| crate::ai::create_player(ai, None, &weights)));
+    game_view.set_weights(weights);
+    game_view.run();
+    Ok(())
+}
+
+/// Replay a game from a file, starting at a ply, or at the end if there is none.
+/// Without a window, the moves up to that ply are shown,
+/// followed by the board as seen by the player to move
+fn run_replay(filename: &str, ply: Option<usize>, headless: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let text = std::fs::read_to_string(filename)?;
+    let mut game = crate::convert::read_game(&text, crate::convert::get_format_from_filename(filename))?;
+    let n_plies = game.get_history().len();
+    let ply = ply.unwrap_or(n_plies);
+    if ply > n_plies {
+        return Err(format!("Ply {} is after the end of the game, which has {} plies", ply, n_plies).into());
+    }
+    while game.get_history().len() > ply {
+        game.undo();
+    }
+    if headless || crate::is_on_gha::is_on_gha() {
+        for entry in game.get_history() {
+            println!("{}", crate::san::get_san(entry.get_position(), entry.get_move()));
+        }
+        print!("{}", crate::headless::get_board_text(game.get_position().get_board(), game.get_current_player()));
+        return Ok(());
+    }
+    let mut game_view = crate::game_view::GameView::new(800, 600);
+    game_view.set_game(game);
+    game_view.run();
+    Ok(())
+}
+
+/// Let the computer play games against itself and show how many each color won,
+/// evaluating positions with the weights read from a file, if any.
+/// The games are written as PGN to a file, if there is one
+fn run_selfplay(
+    games: usize,
+    white: crate::ai::Ai,
+    black: crate::ai::Ai,
+    max_plies: usize,
+    seed: Option<u64>,
+    output: Option<String>,
+    weights: Option<String>
+) -> Result<(), Box<dyn std::error::Error>> {
+    use rand::Rng;
+    use rand::SeedableRng;
+    use crate::game_status::GameStatus;
+
+    let mut rng = match seed {
+        Some(seed) => rand::rngs::StdRng::seed_from_u64(seed),
+        None => rand::rngs::StdRng::from_entropy(),
+    };
+    let weights = read_weights(weights)?;
+    let mut pgns: Vec<String> = Vec::new();
+    let mut counts = [0; 4];
+    for _ in 0..games {
+        let mut white_player = crate::ai::create_player(white, Some(rng.gen()), &weights);
+        let mut black_player = crate::ai::create_player(black, Some(rng.gen()), &weights);
+        let game = crate::player::play_game(&mut *white_player, &mut *black_player, max_plies);
+        let index = match game.get_status() {
+            GameStatus::WhiteWins => 0,
+            GameStatus::BlackWins => 1,
+            GameStatus::Draw => 2,
+            GameStatus::Ongoing => 3,
+        };
+        counts[index] += 1;
+        pgns.push(crate::pgn::to_pgn(&game, false));
+    }
+    println!(
+        "{} (White) versus {} (Black), {} games: {} won by White, {} won by Black, {} drawn, {} unfinished after {} plies",
+        crate::ai::to_str(white), crate::ai::to_str(black), games, counts[0], counts[1], counts[2], counts[3], max_plies
+    );
+    if let Some(filename) = output {
+        std::fs::write(filename, pgns.join("\n"))?;
+    }
+    Ok(())
+}
+
+/// Write a text to a file, or to stdout if there is none
+fn write_output(text: &str, output: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+    match output {
+        Some(filename) => std::fs::write(filename, text)?,
+        None => print!("{}", text),
+    }
+    Ok(())
+}
@@ -0,0 +1,582 @@
+use crate::bitboard::Bitboard;
+use crate::board::Board;
+use crate::board::get_piece_at_square;
+use crate::chess_move::Move;
+use crate::chess_move::create_move;
+use crate::color::Color;
+use crate::piece::Piece;
+use crate::piece_type::PieceType;
+use crate::square::Square;
+use crate::square::get_nth_rank;
+use crate::square::get_square_at_offset;
+
+/// The (file, rank) offsets of a bishop
+const DIAGONAL_OFFSETS: [(i32, i32); 4] = [(1, 1), (1, -1), (-1, -1), (-1, 1)];
+
+/// The (file, rank) offsets of a rook
+const ORTHOGONAL_OFFSETS: [(i32, i32); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+
+/// The (file, rank) offsets of a king and a queen
+const ALL_OFFSETS: [(i32, i32); 8] = [(0, 1), (1, 1), (1, 0), (1, -1), (0, -1), (-1, -1), (-1, 0), (-1, 1)];
+
+/// The (file, rank) offsets of a knight
+const KNIGHT_OFFSETS: [(i32, i32); 8] = [(1, 2), (2, 1), (2, -1), (1, -2), (-1, -2), (-2, -1), (-2, 1), (-1, 2)];
+
+/// Get all the squares the piece at a square can move to,
+/// without considering if this leaves its king under attack.
+///
+/// Returns nothing if there is no piece of the given color at that square.
+///
+/// ```
+/// use search_and_destroy_chess_2::board::Board;
+/// use search_and_destroy_chess_2::color::Color;
+/// use search_and_destroy_chess_2::move_generator::get_pseudo_legal_destinations;
+/// use search_and_destroy_chess_2::square::Square;
+///
+/// let board = Board::new();
+/// let squares = get_pseudo_legal_destinations(&board, &Square::new("g1"), Color::White);
+/// assert_eq!(squares, vec![Square::new("h3"), Square::new("f3")]);
+/// let squares = get_pseudo_legal_destinations(&board, &Square::new("g1"), Color::Black);
+/// assert!(squares.is_empty());
+/// ```
+pub fn get_pseudo_legal_destinations(board: &Board, square: &Square, color: Color) -> Vec<Square> {
+    let piece = match get_piece_at_square(board, square) {
+        Some(piece) => piece,
+        None => return vec![],
+    };
+    if piece.get_color() != color {
+        return vec![]
+    }
+    match piece.get_type() {
+        PieceType::Bishop => get_sliding_destinations(board, square, color, &DIAGONAL_OFFSETS),
+        PieceType::King => get_stepping_destinations(board, square, color, &ALL_OFFSETS),
+        PieceType::Knight => get_stepping_destinations(board, square, color, &KNIGHT_OFFSETS),
+        PieceType::Pawn => get_pawn_destinations(board, square, color),
+        PieceType::Queen => get_sliding_destinations(board, square, color, &ALL_OFFSETS),
+        PieceType::Rook => get_sliding_destinations(board, square, color, &ORTHOGONAL_OFFSETS),
+    }
+}
+
+/// Get all the squares the piece at a square attacks,
+/// i.e. the squares it could capture an enemy piece on.
+/// This includes the squares of pieces of its own color it defends.
+///
+/// Returns nothing if there is no piece at that square.
+///
+/// ```
+/// use search_and_destroy_chess_2::board::Board;
+/// use search_and_destroy_chess_2::move_generator::get_attacked_squares;
+/// use search_and_destroy_chess_2::square::Square;
+///
+/// let board = Board::new();
+/// let squares = get_attacked_squares(&board, &Square::new("e2"));
+/// assert_eq!(squares, vec![Square::new("d3"), Square::new("f3")]);
+/// ```
+pub fn get_attacked_squares(board: &Board, square: &Square) -> Vec<Square> {
+    get_attack_bitboard(board, square).get_squares()
+}
+
+/// Get all the squares the piece at a square attacks, as a bitboard.
+///
+/// Returns an empty bitboard if there is no piece at that square.
+///
+/// ```
+/// use search_and_destroy_chess_2::board::Board;
+/// use search_and_destroy_chess_2::move_generator::get_attack_bitboard;
+/// use search_and_destroy_chess_2::square::Square;
+///
+/// let board = Board::new();
+/// assert_eq!(get_attack_bitboard(&board, &Square::new("d1")).count(), 5);
+/// assert!(get_attack_bitboard(&board, &Square::new("d4")).is_empty());
+/// ```
+pub fn get_attack_bitboard(board: &Board, square: &Square) -> Bitboard {
+    let piece = match get_piece_at_square(board, square) {
+        Some(piece) => piece,
+        None => return Bitboard::empty(),
+    };
+    let occupied = board.get_occupied();
+    match piece.get_type() {
+        PieceType::Bishop => crate::bitboard::get_bishop_attacks(square, occupied),
+        PieceType::King => crate::bitboard::get_king_attacks(square),
+        PieceType::Knight => crate::bitboard::get_knight_attacks(square),
+        PieceType::Pawn => crate::bitboard::get_pawn_attacks(square, piece.get_color()),
+        PieceType::Queen => crate::bitboard::get_queen_attacks(square, occupied),
+        PieceType::Rook => crate::bitboard::get_rook_attacks(square, occupied),
+    }
+}
+
+/// Get all the moves the pieces of a color can do,
+/// without considering if this leaves its king under attack.
+/// A pawn moving to the last rank has one move per piece type it can promote to.
+///
+/// ```
+/// use search_and_destroy_chess_2::board::Board;
+/// use search_and_destroy_chess_2::color::Color;
+/// use search_and_destroy_chess_2::move_generator::get_pseudo_legal_moves;
+///
+/// let board = Board::new();
+/// assert_eq!(get_pseudo_legal_moves(&board, Color::White).len(), 20);
+/// assert_eq!(get_pseudo_legal_moves(&board, Color::Black).len(), 20);
+/// ```
+pub fn get_pseudo_legal_moves(board: &Board, color: Color) -> Vec<Move> {
+    let mut moves: Vec<Move> = Vec::new();
+    for from in crate::square::get_all_squares() {
+        for to in get_pseudo_legal_destinations(board, &from, color) {
+            let chess_move = create_move(board, &from, &to);
+            if crate::position::is_promotion(board, &chess_move) {
+                for piece_type in crate::piece_type::get_promotion_piece_types() {
+                    let mut promotion_move = chess_move.clone();
+                    promotion_move.set_promotion(Some(piece_type));
+                    moves.push(promotion_move);
+                }
+            } else {
+                moves.push(chess_move);
+            }
+        }
+    }
+    moves
+}
+
+/// Get the castling moves of the player to move.
+/// A castling move is the move of the king two squares towards a rook.
+///
+/// To castle, the player must still have the castling right,
+/// the king and rook must be on their starting squares
+/// and the squares between them must be empty.
+/// If the rules do not allow castling through attack,
+/// the squares the king starts on, passes and ends on may not be attacked.
+///
+/// ```
+/// use search_and_destroy_chess_2::move_generator::get_castling_moves;
+/// use search_and_destroy_chess_2::position::Position;
+/// use search_and_destroy_chess_2::rules::Rules;
+///
+/// let position = Position::new();
+/// assert!(get_castling_moves(&position, &Rules::new()).is_empty());
+/// ```
+pub fn get_castling_moves(position: &crate::position::Position, rules: &crate::rules::Rules) -> Vec<Move> {
+    let board = position.get_board();
+    let color = position.get_side_to_move();
+    let castling_rights = position.get_castling_rights();
+    let rank = match color {
+        Color::White => "1",
+        Color::Black => "8",
+    };
+    let create_square = |file: &str| Square::new(&format!("{}{}", file, rank));
+    let mut moves: Vec<Move> = Vec::new();
+    if get_piece_at_square(board, &create_square("e")) != Some(Piece::new(color, PieceType::King)) {
+        return moves
+    }
+    let rook = Some(Piece::new(color, PieceType::Rook));
+    let other_color = crate::color::get_other_color(color);
+    let can_castle = |rook_file: &str, empty_files: &[&str], king_files: &[&str]| {
+        get_piece_at_square(board, &create_square(rook_file)) == rook
+        && empty_files.iter().all(|file| get_piece_at_square(board, &create_square(file)).is_none())
+        && (
+            rules.is_castling_through_attack_allowed()
+            || king_files.iter().all(|file| !is_square_attacked(board, &create_square(file), other_color))
+        )
+    };
+    if castling_rights.can_castle_king_side(color) && can_castle("h", &["f", "g"], &["e", "f", "g"]) {
+        moves.push(Move::new(create_square("e"), create_square("g")));
+    }
+    if castling_rights.can_castle_queen_side(color) && can_castle("a", &["b", "c", "d"], &["e", "d", "c"]) {
+        moves.push(Move::new(create_square("e"), create_square("c")));
+    }
+    moves
+}
+
+/// Get the moves of the pawns of the player to move that capture en passant
+///
+/// ```
+/// use search_and_destroy_chess_2::move_generator::get_en_passant_moves;
+/// use search_and_destroy_chess_2::position::Position;
+///
+/// let position = Position::new();
+/// assert!(get_en_passant_moves(&position).is_empty());
+/// ```
+pub fn get_en_passant_moves(position: &crate::position::Position) -> Vec<Move> {
+    let target = match position.get_en_passant() {
+        Some(target) => target,
+        None => return vec![],
+    };
+    let board = position.get_board();
+    let color = position.get_side_to_move();
+    let backward = match color {
+        Color::White => -1,
+        Color::Black => 1,
+    };
+    let passed_pawn_square = match get_square_at_offset(&target, 0, backward) {
+        Some(square) => square,
+        None => return vec![],
+    };
+    let mut moves: Vec<Move> = Vec::new();
+    for file_offset in [-1, 1] {
+        if let Some(from) = get_square_at_offset(&target, file_offset, backward) {
+            if get_piece_at_square(board, &from) == Some(Piece::new(color, PieceType::Pawn)) {
+                let mut chess_move = Move::new(from, target);
+                chess_move.set_captured(get_piece_at_square(board, &passed_pawn_square));
+                moves.push(chess_move);
+            }
+        }
+    }
+    moves
+}
+
+/// Detect if a square is attacked by any piece of a color
+///
+/// ```
+/// use search_and_destroy_chess_2::board::Board;
+/// use search_and_destroy_chess_2::color::Color;
+/// use search_and_destroy_chess_2::move_generator::is_square_attacked;
+/// use search_and_destroy_chess_2::square::Square;
+///
+/// let board = Board::new();
+/// assert!(is_square_attacked(&board, &Square::new("f3"), Color::White));
+/// assert!(!is_square_attacked(&board, &Square::new("f3"), Color::Black));
+/// ```
+pub fn is_square_attacked(board: &Board, square: &Square, color: Color) -> bool {
+    // A piece attacks a square if that piece would attack it from that square
+    let occupied = board.get_occupied();
+    let pieces = |piece_type: PieceType| board.get_piece_bitboard(color, piece_type);
+    let diagonal_pieces = pieces(PieceType::Bishop) | pieces(PieceType::Queen);
+    let orthogonal_pieces = pieces(PieceType::Rook) | pieces(PieceType::Queen);
+    let other_color = crate::color::get_other_color(color);
+    !(crate::bitboard::get_pawn_attacks(square, other_color) & pieces(PieceType::Pawn)).is_empty()
+        || !(crate::bitboard::get_knight_attacks(square) & pieces(PieceType::Knight)).is_empty()
+        || !(crate::bitboard::get_king_attacks(square) & pieces(PieceType::King)).is_empty()
+        || !(crate::bitboard::get_bishop_attacks(square, occupied) & diagonal_pieces).is_empty()
+        || !(crate::bitboard::get_rook_attacks(square, occupied) & orthogonal_pieces).is_empty()
+}
+
+/// Detect if a square is empty or contains a piece of the other color
+fn is_empty_or_enemy(board: &Board, square: &Square, color: Color) -> bool {
+    match get_piece_at_square(board, square) {
+        Some(piece) => piece.get_color() != color,
+        None => true,
+    }
+}
+
+/// Get the rank offset of one step forward for the pawns of a color,
+/// with the index of the rank they start on
+pub(crate) fn get_pawn_direction(color: Color) -> (i32, usize) {
+    match color {
+        Color::White => (1, 1),
+        Color::Black => (-1, 6),
+    }
+}
+
+/// Get the squares, of some squares, a piece can stand on:
+/// a pawn is never on the first or last rank
+pub(crate) fn get_squares_it_can_stand_on(piece: &Piece, squares: Bitboard) -> Bitboard {
+    match piece.get_type() {
+        PieceType::Pawn => squares & Bitboard::new(0x00FF_FFFF_FFFF_FF00),
+        _ => squares,
+    }
+}
+
+/// Get the squares a pawn can move to: one or two squares forward
+/// if these are empty, or diagonally forward to capture
+fn get_pawn_destinations(board: &Board, square: &Square, color: Color) -> Vec<Square> {
+    let (forward, starting_rank_index) = get_pawn_direction(color);
+    let mut squares: Vec<Square> = Vec::new();
+    if let Some(one_ahead) = get_square_at_offset(square, 0, forward) {
+        if get_piece_at_square(board, &one_ahead).is_none() {
+            squares.push(one_ahead);
+            if get_nth_rank(square) == starting_rank_index {
+                if let Some(two_ahead) = get_square_at_offset(square, 0, 2 * forward) {
+                    if get_piece_at_square(board, &two_ahead).is_none() {
+                        squares.push(two_ahead);
+                    }
+                }
+            }
+        }
+    }
+    for file_offset in [-1, 1] {
+        if let Some(diagonal) = get_square_at_offset(square, file_offset, forward) {
+            if let Some(piece) = get_piece_at_square(board, &diagonal) {
+                if piece.get_color() != color {
+                    squares.push(diagonal);
+                }
+            }
+        }
+    }
+    squares
+}
+
+/// Get the squares a sliding piece (i.e. a bishop, queen or rook) can move to.
+/// Sliding stops at the first piece encountered, which can be captured
+/// if it is of the other color
+fn get_sliding_destinations(board: &Board, square: &Square, color: Color, offsets: &[(i32, i32)]) -> Vec<Square> {
+    let mut squares: Vec<Square> = Vec::new();
+    for (file_offset, rank_offset) in offsets {
+        let mut distance = 1;
+        while let Some(target) = get_square_at_offset(square, distance * file_offset, distance * rank_offset) {
+            match get_piece_at_square(board, &target) {
+                Some(piece) => {
+                    if piece.get_color() != color {
+                        squares.push(target);
+                    }
+                    break;
+                },
+                None => squares.push(target),
+            }
+            distance += 1;
+        }
+    }
+    squares
+}
+
+/// Get the squares a stepping piece (i.e. a king or knight) can move to
+fn get_stepping_destinations(board: &Board, square: &Square, color: Color, offsets: &[(i32, i32)]) -> Vec<Square> {
+    offsets.iter()
+        .filter_map(|(file_offset, rank_offset)| get_square_at_offset(square, *file_offset, *rank_offset))
+        .filter(|target| is_empty_or_enemy(board, target, color))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::castling_rights::CastlingRights;
+    use crate::piece::*;
+    use crate::position::Position;
+    use crate::rules::Rules;
+    use crate::rank::Rank;
+    use crate::square::get_nth_file;
+
+    /// Create a board with only the given pieces
+    fn create_board(pieces: Vec<(&str, Piece)>) -> Board {
+        let mut rows: Vec<Vec<Option<Piece>>> = vec![vec![None; 8]; 8];
+        for (coordinat, piece) in pieces {
+            let square = Square::new(coordinat);
+            rows[get_nth_rank(&square)][get_nth_file(&square).get()] = Some(piece);
+        }
+        Board::from_ranks(rows.into_iter().map(Rank::new).collect())
+    }
+
+    fn to_coordinats(squares: Vec<Square>) -> Vec<String> {
+        let mut coordinats: Vec<String> = squares.iter().map(|square| square.get()).collect();
+        coordinats.sort();
+        coordinats
+    }
+
+    #[test]
+    fn no_piece_has_no_destinations() {
+        let board = Board::new();
+        assert!(get_pseudo_legal_destinations(&board, &Square::new("e4"), Color::White).is_empty());
+    }
+    #[test]
+    fn piece_of_other_color_has_no_destinations() {
+        let board = Board::new();
+        assert!(get_pseudo_legal_destinations(&board, &Square::new("e7"), Color::White).is_empty());
+    }
+    #[test]
+    fn pawns_at_start() {
+        let board = Board::new();
+        assert_eq!(
+            to_coordinats(get_pseudo_legal_destinations(&board, &Square::new("e2"), Color::White)),
+            vec!["e3", "e4"]
+        );
+        assert_eq!(
+            to_coordinats(get_pseudo_legal_destinations(&board, &Square::new("d7"), Color::Black)),
+            vec!["d5", "d6"]
+        );
+    }
+    #[test]
+    fn pawn_captures_diagonally_and_is_blocked() {
+        let board = create_board(vec![
+            ("e2", create_white_pawn()),
+            ("e3", create_black_knight()),
+            ("d3", create_black_pawn()),
+            ("f3", create_white_pawn()),
+        ]);
+        assert_eq!(
+            to_coordinats(get_pseudo_legal_destinations(&board, &Square::new("e2"), Color::White)),
+            vec!["d3"]
+        );
+    }
+    #[test]
+    fn pawn_double_push_needs_both_squares_empty() {
+        let board = create_board(vec![
+            ("c7", create_black_pawn()),
+            ("c5", create_white_bishop()),
+        ]);
+        assert_eq!(
+            to_coordinats(get_pseudo_legal_destinations(&board, &Square::new("c7"), Color::Black)),
+            vec!["c6"]
+        );
+    }
+    #[test]
+    fn pawn_not_at_start_moves_one() {
+        let board = create_board(vec![("a3", create_white_pawn())]);
+        assert_eq!(
+            to_coordinats(get_pseudo_legal_destinations(&board, &Square::new("a3"), Color::White)),
+            vec!["a4"]
+        );
+    }
+    #[test]
+    fn bishop_stops_at_blockers() {
+        let board = create_board(vec![
+            ("c1", create_white_bishop()),
+            ("e3", create_white_pawn()),
+            ("a3", create_black_pawn()),
+        ]);
+        assert_eq!(
+            to_coordinats(get_pseudo_legal_destinations(&board, &Square::new("c1"), Color::White)),
+            vec!["a3", "b2", "d2"]
+        );
+    }
+    #[test]
+    fn king_in_corner() {
+        let board = create_board(vec![
+            ("h8", create_black_king()),
+            ("g8", create_black_rook()),
+            ("g7", create_white_queen()),
+        ]);
+        assert_eq!(
+            to_coordinats(get_pseudo_legal_destinations(&board, &Square::new("h8"), Color::Black)),
+            vec!["g7", "h7"]
+        );
+    }
+    #[test]
+    fn knights_at_start() {
+        let board = Board::new();
+        assert_eq!(
+            to_coordinats(get_pseudo_legal_destinations(&board, &Square::new("b1"), Color::White)),
+            vec!["a3", "c3"]
+        );
+        assert_eq!(
+            to_coordinats(get_pseudo_legal_destinations(&board, &Square::new("g8"), Color::Black)),
+            vec!["f6", "h6"]
+        );
+    }
+    #[test]
+    fn queen_in_center() {
+        let board = create_board(vec![("d4", create_white_queen())]);
+        assert_eq!(get_pseudo_legal_destinations(&board, &Square::new("d4"), Color::White).len(), 27);
+    }
+    #[test]
+    fn rook_stops_at_blockers() {
+        let board = create_board(vec![
+            ("d4", create_black_rook()),
+            ("d6", create_black_pawn()),
+            ("f4", create_white_pawn()),
+        ]);
+        assert_eq!(
+            to_coordinats(get_pseudo_legal_destinations(&board, &Square::new("d4"), Color::Black)),
+            vec!["a4", "b4", "c4", "d1", "d2", "d3", "d5", "e4", "f4"]
+        );
+    }
+    #[test]
+    fn empty_square_attacks_nothing() {
+        assert!(get_attacked_squares(&Board::new(), &Square::new("e4")).is_empty());
+    }
+    #[test]
+    fn black_pawn_attacks_downwards() {
+        let board = create_board(vec![("a7", create_black_pawn())]);
+        assert_eq!(to_coordinats(get_attacked_squares(&board, &Square::new("a7"))), vec!["b6"]);
+    }
+    #[test]
+    fn rook_attacks_include_defended_pieces() {
+        let board = create_board(vec![
+            ("a1", create_white_rook()),
+            ("a3", create_white_pawn()),
+            ("c1", create_black_knight()),
+        ]);
+        assert_eq!(
+            to_coordinats(get_attacked_squares(&board, &Square::new("a1"))),
+            vec!["a2", "a3", "b1", "c1"]
+        );
+    }
+    #[test]
+    fn knight_attacks_include_own_pieces() {
+        let board = Board::new();
+        assert_eq!(
+            to_coordinats(get_attacked_squares(&board, &Square::new("b1"))),
+            vec!["a3", "c3", "d2"]
+        );
+    }
+    #[test]
+    fn moves_record_captures() {
+        let board = create_board(vec![
+            ("a1", create_white_rook()),
+            ("a5", create_black_queen()),
+        ]);
+        let moves = get_pseudo_legal_moves(&board, Color::White);
+        assert_eq!(moves.len(), 11);
+        let captures: Vec<&Move> = moves.iter().filter(|chess_move| chess_move.is_capture()).collect();
+        assert_eq!(captures.len(), 1);
+        assert_eq!(captures[0].get_to(), Square::new("a5"));
+        assert_eq!(captures[0].get_captured(), Some(create_black_queen()));
+    }
+    #[test]
+    fn castle_both_sides() {
+        let board = create_board(vec![
+            ("a8", create_black_rook()),
+            ("e8", create_black_king()),
+            ("h8", create_black_rook()),
+        ]);
+        let position = Position::from_board(board, Color::Black, CastlingRights::new());
+        let moves = get_castling_moves(&position, &Rules::new());
+        assert_eq!(moves, vec![
+            Move::new(Square::new("e8"), Square::new("g8")),
+            Move::new(Square::new("e8"), Square::new("c8")),
+        ]);
+        assert_eq!(position.get_pseudo_legal_moves(&Rules::new()).len(), 2 + 5 + 10 + 9);
+    }
+    #[test]
+    fn no_castling_without_rights_or_rook() {
+        let board = create_board(vec![
+            ("a1", create_white_rook()),
+            ("e1", create_white_king()),
+            ("h1", create_white_knight()),
+        ]);
+        let mut castling_rights = CastlingRights::new();
+        let position = Position::from_board(board.clone(), Color::White, castling_rights);
+        assert_eq!(get_castling_moves(&position, &Rules::new()), vec![Move::new(Square::new("e1"), Square::new("c1"))]);
+        castling_rights.set_queen_side(Color::White, false);
+        let position = Position::from_board(board, Color::White, castling_rights);
+        assert!(get_castling_moves(&position, &Rules::new()).is_empty());
+    }
+    #[test]
+    fn no_castling_through_pieces() {
+        let board = create_board(vec![
+            ("a1", create_white_rook()),
+            ("b1", create_black_knight()),
+            ("e1", create_white_king()),
+        ]);
+        let position = Position::from_board(board, Color::White, CastlingRights::new());
+        assert!(get_castling_moves(&position, &Rules::new()).is_empty());
+    }
+    #[test]
+    fn castling_through_attack_is_configurable() {
+        let board = create_board(vec![
+            ("e1", create_white_king()),
+            ("h1", create_white_rook()),
+            ("f8", create_black_rook()),
+        ]);
+        let position = Position::from_board(board, Color::White, CastlingRights::new());
+        let mut rules = Rules::new();
+        assert_eq!(get_castling_moves(&position, &rules).len(), 1);
+        rules.set_castling_through_attack_allowed(false);
+        assert!(get_castling_moves(&position, &rules).is_empty());
+    }
+    #[test]
+    fn pawn_on_last_rank_must_promote() {
+        let board = create_board(vec![
+            ("b2", create_black_pawn()),
+            ("a1", create_white_rook()),
+        ]);
+        let moves = get_pseudo_legal_moves(&board, Color::Black);
+        assert_eq!(moves.len(), 8);
+        assert!(moves.iter().all(|chess_move| chess_move.get_promotion().is_some()));
+        assert_eq!(moves.iter().filter(|chess_move| chess_move.is_capture()).count(), 4);
+    }
+    #[test]
+    fn rook_at_start_cannot_move() {
+        let board = Board::new();
+        assert!(get_pseudo_legal_destinations(&board, &Square::new("a1"), Color::White).is_empty());
+        assert!(get_pseudo_legal_destinations(&board, &Square::new("h8"), Color::Black).is_empty());
+    }
+}
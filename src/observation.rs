@@ -0,0 +1,158 @@
+use crate::bitboard::Bitboard;
+use crate::chess_move::Move;
+use crate::color::Color;
+use crate::position::Position;
+use crate::rules::Rules;
+
+/// What a player knows when it is its turn:
+/// the position with the squares it cannot see left empty,
+/// which squares it can see, the moves it seems to be able to do,
+/// the enemy pieces it has captured so far,
+/// and the moves it tried this turn that were rejected.
+/// The moves are found from what the player sees,
+/// so a piece it cannot see may block one of them,
+/// such as a pawn moving two squares forward.
+/// Such a move is rejected by the game, after which the player chooses again.
+///
+/// Nothing hidden by the fog of war can be learned from an observation:
+/// the other player's castling rights, the halfmove clock
+/// and an en-passant square that cannot be seen are left out
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Observation {
+    position: Position,
+    visible: Bitboard,
+    moves: Vec<Move>,
+    rules: Rules,
+    captured: Vec<crate::piece::Piece>,
+    rejected: Vec<Move>,
+}
+
+impl Observation {
+
+    /// Create the observation of the player to move in a position
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::color::Color;
+    /// use search_and_destroy_chess_2::observation::Observation;
+    /// use search_and_destroy_chess_2::position::Position;
+    /// use search_and_destroy_chess_2::rules::Rules;
+    ///
+    /// let observation = Observation::new(&Position::new(), &Rules::new());
+    /// assert_eq!(observation.get_color(), Color::White);
+    /// assert_eq!(observation.get_moves().len(), 20);
+    /// assert!(observation.get_position().get_board().get_color_bitboard(Color::Black).is_empty());
+    /// ```
+    pub fn new(position: &Position, rules: &Rules) -> Observation {
+        let color = position.get_side_to_move();
+        let board = position.get_board();
+        let visible = crate::board::get_visible_bitboard(board, color);
+        let other_color = crate::color::get_other_color(color);
+        let mut castling_rights = position.get_castling_rights();
+        castling_rights.set_king_side(other_color, false);
+        castling_rights.set_queen_side(other_color, false);
+        let mut fogged_position = Position::from_board(
+            crate::board::get_fogged_board(board, color),
+            color,
+            castling_rights
+        );
+        fogged_position.set_en_passant(position.get_en_passant().filter(|square| visible.contains(square)));
+        fogged_position.set_fullmove_number(position.get_fullmove_number());
+        let moves = fogged_position.get_pseudo_legal_moves(rules);
+        Observation {
+            position: fogged_position,
+            visible,
+            moves,
+            rules: *rules,
+            captured: Vec::new(),
+            rejected: Vec::new(),
+        }
+    }
+
+    /// Get the enemy pieces the player has captured so far
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::game::Game;
+    /// use search_and_destroy_chess_2::piece::create_black_pawn;
+    /// use search_and_destroy_chess_2::save::game_from_save;
+    ///
+    /// let game = game_from_save("e2e4\nd7d5\ne4d5\ng8f6\n").unwrap();
+    /// assert_eq!(game.get_observation().get_captured(), &vec![create_black_pawn()]);
+    /// ```
+    pub fn get_captured(&self) -> &Vec<crate::piece::Piece> { &self.captured }
+
+    /// Get the color of the player that observes
+    pub fn get_color(&self) -> Color { self.position.get_side_to_move() }
+
+    /// Get the moves the player can do
+    pub fn get_moves(&self) -> &Vec<Move> { &self.moves }
+
+    /// Get the moves the player tried this turn that could not be done
+    pub fn get_rejected(&self) -> &Vec<Move> { &self.rejected }
+
+    /// Get the position as the player sees it, i.e. with the squares it cannot see left empty
+    pub fn get_position(&self) -> &Position { &self.position }
+
+    /// Get the rules the game is played by
+    pub fn get_rules(&self) -> Rules { self.rules }
+
+    /// Get the squares the player can see
+    pub fn get_visible(&self) -> Bitboard { self.visible }
+
+    /// Reject a move the player tried, as a piece it cannot see blocks it,
+    /// after which the move is no longer one the player can choose
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::game::Game;
+    ///
+    /// let mut observation = Game::new().get_observation();
+    /// let chess_move = observation.get_moves()[0].clone();
+    /// observation.reject(&chess_move);
+    /// assert_eq!(observation.get_moves().len(), 19);
+    /// assert_eq!(observation.get_rejected(), &vec![chess_move]);
+    /// ```
+    pub fn reject(&mut self, chess_move: &Move) {
+        self.moves.retain(|other| !crate::chess_move::is_same_move(other, chess_move));
+        self.rejected.push(chess_move.clone());
+    }
+
+    /// Set the enemy pieces the player has captured so far
+    pub fn set_captured(&mut self, captured: Vec<crate::piece::Piece>) { self.captured = captured }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hidden_pieces_are_left_out() {
+        let position = crate::fen::position_from_fen("rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2").unwrap();
+        let observation = Observation::new(&position, &Rules::new());
+        let board = observation.get_position().get_board();
+        // The pawn on e5 blocks the pawn on e4, yet no white piece can move to or attack e5
+        assert!(board.get_color_bitboard(Color::Black).is_empty());
+        assert!(!observation.get_visible().contains(&crate::square::Square::new("e5")));
+        assert_eq!(observation.get_position().get_en_passant(), None);
+        assert!(!observation.get_position().get_castling_rights().can_castle_king_side(Color::Black));
+        assert!(observation.get_position().get_castling_rights().can_castle_king_side(Color::White));
+    }
+    #[test]
+    fn moves_are_found_from_what_is_seen() {
+        // A pawn on b4 that cannot be seen blocks the double push b2-b4,
+        // which the observer cannot know from its moves
+        let position = crate::fen::position_from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+        let observation = Observation::new(&position, &Rules::new());
+        assert_eq!(observation.get_moves(), &observation.get_position().get_pseudo_legal_moves(&Rules::new()));
+        let real_moves = position.get_pseudo_legal_moves(&Rules::new());
+        let blocked_moves: Vec<String> = observation.get_moves().iter()
+            .filter(|chess_move| !real_moves.iter().any(|real_move| crate::chess_move::is_same_move(real_move, chess_move)))
+            .map(crate::chess_move::to_str)
+            .collect();
+        assert_eq!(blocked_moves, vec!["b2b4".to_string()]);
+    }
+    #[test]
+    fn en_passant_is_seen_by_the_pawn_that_can_capture() {
+        let position = crate::fen::position_from_fen("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3").unwrap();
+        let observation = Observation::new(&position, &Rules::new());
+        assert_eq!(observation.get_position().get_en_passant(), Some(crate::square::Square::new("f6")));
+    }
+}
@@ -0,0 +1,236 @@
+use crate::chess_move::Move;
+use crate::color::Color;
+use crate::piece_type::PieceType;
+use crate::position::Position;
+use crate::rules::Rules;
+
+/// Get the moves of the player to move in regular chess,
+/// i.e. the moves that do not leave the own king attacked
+/// and no castling out of, through or into an attacked square.
+///
+/// Search And Destroy Chess has no check, so this is only used to compare
+/// the move generator with the well-known numbers of regular chess.
+///
+/// ```
+/// use search_and_destroy_chess_2::fen::position_from_fen;
+/// use search_and_destroy_chess_2::perft::get_legal_moves;
+///
+/// // The king on e1 is attacked by the rook on e8, so only king moves remain
+/// let position = position_from_fen("4r1k1/8/8/8/8/8/8/R3K3 w Q - 0 1").unwrap();
+/// assert_eq!(get_legal_moves(&position).len(), 4);
+/// ```
+pub fn get_legal_moves(position: &Position) -> Vec<Move> {
+    let mut rules = Rules::new();
+    rules.set_castling_through_attack_allowed(false);
+    let mover = position.get_side_to_move();
+    position.get_pseudo_legal_moves(&rules)
+        .into_iter()
+        .filter(|chess_move| {
+            let mut next_position = position.clone();
+            next_position.apply_move(chess_move);
+            !is_king_attacked(&next_position, mover)
+        })
+        .collect()
+}
+
+/// Count the positions reached after all sequences of moves
+/// of a number of plies, in regular chess.
+/// This is the standard way to test a move generator
+///
+/// ```
+/// use search_and_destroy_chess_2::perft::perft;
+/// use search_and_destroy_chess_2::position::Position;
+///
+/// assert_eq!(perft(&Position::new(), 1), 20);
+/// assert_eq!(perft(&Position::new(), 2), 400);
+/// ```
+pub fn perft(position: &Position, depth: usize) -> u64 {
+    count_nodes(position, depth, &get_legal_moves, &|_| false)
+}
+
+/// Count the positions reached after each move of the player to move,
+/// as `perft` does for one ply less.
+/// This shows which move has a wrong count when comparing to another program
+///
+/// ```
+/// use search_and_destroy_chess_2::perft::divide;
+/// use search_and_destroy_chess_2::position::Position;
+///
+/// let counts = divide(&Position::new(), 2);
+/// assert_eq!(counts.len(), 20);
+/// assert!(counts.iter().all(|(_, count)| *count == 20));
+/// ```
+pub fn divide(position: &Position, depth: usize) -> Vec<(Move, u64)> {
+    divide_nodes(position, depth, &get_legal_moves, &|_| false)
+}
+
+/// Count the positions reached after all sequences of moves
+/// of a number of plies, in Search And Destroy Chess.
+/// All moves are counted, including the ones that leave the own king attacked
+/// and the ones that capture the king.
+/// As capturing the king ends the game, there are no moves after it
+///
+/// ```
+/// use search_and_destroy_chess_2::fen::position_from_fen;
+/// use search_and_destroy_chess_2::perft::perft;
+/// use search_and_destroy_chess_2::perft::perft_king_capture;
+/// use search_and_destroy_chess_2::rules::Rules;
+///
+/// let position = position_from_fen("4k3/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+/// assert_eq!(perft(&position, 1), 15);
+/// assert_eq!(perft_king_capture(&position, &Rules::new(), 1), 15);
+/// // In regular chess, the king may not move next to the other king
+/// assert!(perft_king_capture(&position, &Rules::new(), 2) > perft(&position, 2));
+/// ```
+pub fn perft_king_capture(position: &Position, rules: &Rules, depth: usize) -> u64 {
+    count_nodes(position, depth, &|position| position.get_pseudo_legal_moves(rules), &is_king_captured)
+}
+
+/// Count the positions reached after each move of the player to move,
+/// as `perft_king_capture` does for one ply less
+pub fn divide_king_capture(position: &Position, rules: &Rules, depth: usize) -> Vec<(Move, u64)> {
+    divide_nodes(position, depth, &|position| position.get_pseudo_legal_moves(rules), &is_king_captured)
+}
+
+/// Write the counts of `divide` with one move per line, in coordinate notation,
+/// followed by the total, e.g. 'e2e4: 20'
+///
+/// ```
+/// use search_and_destroy_chess_2::perft::divide;
+/// use search_and_destroy_chess_2::perft::format_divide;
+/// use search_and_destroy_chess_2::position::Position;
+///
+/// let text = format_divide(&divide(&Position::new(), 1));
+/// assert!(text.contains("e2e4: 1\n"));
+/// assert!(text.ends_with("\nNodes searched: 20\n"));
+/// ```
+pub fn format_divide(counts: &[(Move, u64)]) -> String {
+    let mut text = String::new();
+    for (chess_move, count) in counts {
+        text.push_str(&format!("{}: {}\n", crate::chess_move::to_str(chess_move), count));
+    }
+    let total: u64 = counts.iter().map(|(_, count)| count).sum();
+    text.push_str(&format!("\nNodes searched: {}\n", total));
+    text
+}
+
+/// Count the leaf nodes of the tree of moves, where a position is a leaf
+/// when the depth is reached or when `is_game_over` says so
+fn count_nodes(
+    position: &Position,
+    depth: usize,
+    get_moves: &dyn Fn(&Position) -> Vec<Move>,
+    is_game_over: &dyn Fn(&Position) -> bool
+) -> u64 {
+    if depth == 0 || is_game_over(position) {
+        return 1
+    }
+    let moves = get_moves(position);
+    if depth == 1 {
+        return moves.len() as u64
+    }
+    moves.iter()
+        .map(|chess_move| {
+            let mut next_position = position.clone();
+            next_position.apply_move(chess_move);
+            count_nodes(&next_position, depth - 1, get_moves, is_game_over)
+        })
+        .sum()
+}
+
+/// Count the leaf nodes of the tree of moves after each move
+fn divide_nodes(
+    position: &Position,
+    depth: usize,
+    get_moves: &dyn Fn(&Position) -> Vec<Move>,
+    is_game_over: &dyn Fn(&Position) -> bool
+) -> Vec<(Move, u64)> {
+    assert!(depth >= 1, "Divide needs a depth of at least 1");
+    get_moves(position)
+        .into_iter()
+        .map(|chess_move| {
+            let mut next_position = position.clone();
+            next_position.apply_move(&chess_move);
+            let count = count_nodes(&next_position, depth - 1, get_moves, is_game_over);
+            (chess_move, count)
+        })
+        .collect()
+}
+
+/// Detect if the king of a color is attacked by the other color.
+/// A color without a king is not attacked
+fn is_king_attacked(position: &Position, color: Color) -> bool {
+    let board = position.get_board();
+    let other_color = crate::color::get_other_color(color);
+    board.get_piece_bitboard(color, PieceType::King)
+        .get_squares()
+        .iter()
+        .any(|square| crate::move_generator::is_square_attacked(board, square, other_color))
+}
+
+/// Detect if the player to move has lost its king
+fn is_king_captured(position: &Position) -> bool {
+    position.get_board().get_piece_bitboard(position.get_side_to_move(), PieceType::King).is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Check the perft counts of a position, given as FEN, from depth 1
+    fn check_perft(fen: &str, expected_counts: &[u64]) {
+        let position = crate::fen::position_from_fen(fen).unwrap();
+        for (index, expected_count) in expected_counts.iter().enumerate() {
+            assert_eq!(perft(&position, index + 1), *expected_count, "{} at depth {}", fen, index + 1);
+        }
+    }
+
+    #[test]
+    fn starting_position() {
+        check_perft(crate::fen::STARTING_FEN, &[20, 400, 8902]);
+    }
+    #[test]
+    fn kiwipete() {
+        check_perft("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1", &[48, 2039]);
+    }
+    #[test]
+    fn position_3() {
+        check_perft("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", &[14, 191, 2812]);
+    }
+    #[test]
+    fn position_4() {
+        check_perft("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1", &[6, 264, 9467]);
+    }
+    #[test]
+    fn position_5() {
+        check_perft("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8", &[44, 1486]);
+    }
+    #[test]
+    fn divide_sums_to_perft() {
+        let position = crate::fen::position_from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+        let counts = divide(&position, 2);
+        assert_eq!(counts.len(), 48);
+        assert_eq!(counts.iter().map(|(_, count)| count).sum::<u64>(), 2039);
+    }
+    #[test]
+    fn king_capture_starting_position() {
+        // Without check, the first three plies of the game are the same as in regular chess
+        let position = Position::new();
+        assert_eq!(perft_king_capture(&position, &Rules::new(), 3), 8902);
+    }
+    #[test]
+    fn king_capture_ends_the_game() {
+        // White can capture the king, after which Black has no moves
+        let position = crate::fen::position_from_fen("4k3/8/8/8/8/8/8/4R1K1 w - - 0 1").unwrap();
+        let counts = divide_king_capture(&position, &Rules::new(), 2);
+        let king_capture = counts.iter().find(|(chess_move, _)| crate::chess_move::to_str(chess_move) == "e1e8").unwrap();
+        assert_eq!(king_capture.1, 1);
+    }
+    #[test]
+    fn king_capture_counts_moves_into_attack() {
+        // The black king may step next to the rook's file, which regular chess forbids
+        let position = crate::fen::position_from_fen("4k3/8/8/8/8/8/8/3R2K1 b - - 0 1").unwrap();
+        assert_eq!(perft(&position, 1), 3);
+        assert_eq!(perft_king_capture(&position, &Rules::new(), 1), 5);
+    }
+}
@@ -0,0 +1,387 @@
+use crate::color::Color;
+use crate::game::Game;
+use crate::game_status::GameStatus;
+use crate::position::Position;
+
+/// The maximum length of a line of movetext in a PGN
+const MAX_LINE_LENGTH: usize = 80;
+
+/// The reasons why a PGN cannot be replayed.
+/// An error in a move gives its ply, i.e. the number of the halfmove, starting at 1
+///
+/// ```
+/// use search_and_destroy_chess_2::pgn::PgnError;
+/// use search_and_destroy_chess_2::pgn::game_from_pgn;
+///
+/// let error = game_from_pgn("1. e4 e5 2. Ke3 *").unwrap_err();
+/// assert_eq!(error.to_string(), "Ply 3: Move 'Ke3' cannot be done in this position");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PgnError {
+    InvalidFen(crate::fen::FenError),
+    InvalidMove { ply: usize, error: crate::san::SanError },
+    MoveAfterGameOver { ply: usize, san: String },
+    PlyOutOfRange { ply: usize, n_plies: usize },
+    UnclosedComment,
+    UnclosedVariation,
+}
+
+impl std::fmt::Display for PgnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PgnError::InvalidFen(error) => write!(f, "Invalid FEN tag: {}", error),
+            PgnError::InvalidMove { ply, error } => write!(f, "Ply {}: {}", ply, error),
+            PgnError::MoveAfterGameOver { ply, san } => write!(f, "Ply {}: Move '{}' is done after the game is over", ply, san),
+            PgnError::PlyOutOfRange { ply, n_plies } => write!(f, "Cannot go to ply {}, the game has {} plies", ply, n_plies),
+            PgnError::UnclosedComment => write!(f, "A comment is opened with '{{' but never closed"),
+            PgnError::UnclosedVariation => write!(f, "A variation is opened with '(' but never closed"),
+        }
+    }
+}
+
+impl std::error::Error for PgnError {}
+
+/// Read a game from a PGN, doing all its moves.
+/// The game starts from the position in the FEN tag, if there is one
+///
+/// ```
+/// use search_and_destroy_chess_2::game_status::GameStatus;
+/// use search_and_destroy_chess_2::pgn::game_from_pgn;
+///
+/// let game = game_from_pgn("[Result \"1-0\"]\n\n1. e3 f6 2. Qh5 a6 3. Qxe8 1-0").unwrap();
+/// assert_eq!(game.get_history().len(), 5);
+/// assert_eq!(game.get_status(), GameStatus::WhiteWins);
+/// ```
+pub fn game_from_pgn(pgn: &str) -> Result<Game, PgnError> {
+    let n_plies = get_sans(pgn)?.len();
+    game_from_pgn_at_ply(pgn, n_plies)
+}
+
+/// Read a game from a PGN, doing only its first moves,
+/// e.g. to look at the position after a ply.
+/// At ply 0, no moves are done yet.
+/// All moves up to that ply must be valid,
+/// the moves after it are not checked
+///
+/// ```
+/// use search_and_destroy_chess_2::color::Color;
+/// use search_and_destroy_chess_2::pgn::game_from_pgn_at_ply;
+///
+/// let pgn = "1. e4 {A comment} e5 (1... c5 2. Nf3) 2. Nf3 $1 Nc6 *";
+/// let game = game_from_pgn_at_ply(pgn, 3).unwrap();
+/// assert_eq!(game.get_history().len(), 3);
+/// assert_eq!(game.get_current_player(), Color::Black);
+/// assert!(game_from_pgn_at_ply(pgn, 5).is_err());
+/// ```
+pub fn game_from_pgn_at_ply(pgn: &str, ply: usize) -> Result<Game, PgnError> {
+    let sans = get_sans(pgn)?;
+    if ply > sans.len() {
+        return Err(PgnError::PlyOutOfRange { ply, n_plies: sans.len() });
+    }
+    let mut game = Game::new();
+    if let Some(fen) = get_tag(pgn, "FEN") {
+        game.set_position(crate::fen::position_from_fen(&fen).map_err(PgnError::InvalidFen)?);
+    }
+    for (index, san) in sans.iter().take(ply).enumerate() {
+        if crate::game_status::is_over(game.get_status()) {
+            return Err(PgnError::MoveAfterGameOver { ply: index + 1, san: san.clone() });
+        }
+        let chess_move = crate::san::get_move_from_san(game.get_position(), &game.get_rules(), san)
+            .map_err(|error| PgnError::InvalidMove { ply: index + 1, error })?;
+        game.do_move(&chess_move);
+    }
+    Ok(game)
+}
+
+/// Get the result of a game as written in a PGN, e.g. '1-0' if White has won.
+/// A game that is not over has result '*'
+///
+/// ```
+/// use search_and_destroy_chess_2::game_status::GameStatus;
+/// use search_and_destroy_chess_2::pgn::get_result;
+///
+/// assert_eq!(get_result(GameStatus::WhiteWins), "1-0");
+/// assert_eq!(get_result(GameStatus::BlackWins), "0-1");
+/// assert_eq!(get_result(GameStatus::Draw), "1/2-1/2");
+/// assert_eq!(get_result(GameStatus::Ongoing), "*");
+/// ```
+pub fn get_result(status: GameStatus) -> String {
+    match status {
+        GameStatus::WhiteWins => "1-0".to_string(),
+        GameStatus::BlackWins => "0-1".to_string(),
+        GameStatus::Draw => "1/2-1/2".to_string(),
+        GameStatus::Ongoing => "*".to_string(),
+    }
+}
+
+/// Get the comment describing what the player to move can see in a position,
+/// i.e. the enemy pieces that are not hidden by the fog of war,
+/// e.g. '{Sees Pd5 Nf6}'
+///
+/// ```
+/// use search_and_destroy_chess_2::pgn::get_fog_comment;
+/// use search_and_destroy_chess_2::position::Position;
+///
+/// assert_eq!(get_fog_comment(&Position::new()), "{Sees nothing}");
+/// ```
+pub fn get_fog_comment(position: &Position) -> String {
+    let board = position.get_board();
+    let color = position.get_side_to_move();
+    let seen: Vec<String> = crate::board::get_visible_squares(board, color)
+        .into_iter()
+        .filter_map(|square| {
+            let piece = crate::board::get_piece_at_square(board, &square)?;
+            match piece.get_color() == color {
+                true => None,
+                false => Some(format!("{}{}", crate::piece_type::to_char(piece.get_type()), square.get())),
+            }
+        })
+        .collect();
+    match seen.is_empty() {
+        true => "{Sees nothing}".to_string(),
+        false => format!("{{Sees {}}}", seen.join(" ")),
+    }
+}
+
+/// Write a game as a PGN, with the moves in standard algebraic notation.
+/// With fog comments, each move is followed by a comment
+/// with the enemy pieces the player could see when doing that move.
+/// A game that did not start from the starting position
+/// has its first position in a FEN tag
+///
+/// ```
+/// use search_and_destroy_chess_2::chess_move::Move;
+/// use search_and_destroy_chess_2::game::Game;
+/// use search_and_destroy_chess_2::pgn::to_pgn;
+/// use search_and_destroy_chess_2::square::Square;
+///
+/// let mut game = Game::new();
+/// game.do_move(&Move::new(Square::new("e2"), Square::new("e4")));
+/// game.do_move(&Move::new(Square::new("g8"), Square::new("f6")));
+/// let pgn = to_pgn(&game, false);
+/// assert!(pgn.contains("[Result \"*\"]"));
+/// assert!(pgn.ends_with("1. e4 Nf6 *\n"));
+/// ```
+pub fn to_pgn(game: &Game, with_fog_comments: bool) -> String {
+    let result = get_result(game.get_status());
+    let first_position = match game.get_history().first() {
+        Some(entry) => entry.get_position(),
+        None => game.get_position(),
+    };
+    let mut pgn = String::new();
+    let mut tags = vec![
+        ("Event", "Search And Destroy Chess".to_string()),
+        ("Site", "?".to_string()),
+        ("Date", "????.??.??".to_string()),
+        ("Round", "?".to_string()),
+        ("White", "?".to_string()),
+        ("Black", "?".to_string()),
+        ("Result", result.clone()),
+    ];
+    if *first_position != Position::new() {
+        tags.push(("SetUp", "1".to_string()));
+        tags.push(("FEN", crate::fen::position_to_fen(first_position)));
+    }
+    for (name, value) in tags {
+        pgn.push_str(&format!("[{} \"{}\"]\n", name, value));
+    }
+    pgn.push('\n');
+
+    let mut tokens: Vec<String> = Vec::new();
+    let mut needs_move_number = true;
+    for entry in game.get_history() {
+        let position = entry.get_position();
+        match position.get_side_to_move() {
+            Color::White => tokens.push(format!("{}.", position.get_fullmove_number())),
+            Color::Black if needs_move_number => tokens.push(format!("{}...", position.get_fullmove_number())),
+            Color::Black => {},
+        }
+        tokens.push(crate::san::get_san(position, entry.get_move()));
+        if with_fog_comments {
+            tokens.push(get_fog_comment(position));
+        }
+        needs_move_number = with_fog_comments;
+    }
+    tokens.push(result);
+    pgn.push_str(&wrap_tokens(&tokens));
+    pgn.push('\n');
+    pgn
+}
+
+/// Get the value of a tag in a PGN, e.g. '1-0' for the tag 'Result'
+///
+/// ```
+/// use search_and_destroy_chess_2::pgn::get_tag;
+///
+/// let pgn = "[Event \"Club match\"]\n[Result \"1-0\"]\n\n1. e4 1-0";
+/// assert_eq!(get_tag(pgn, "Result"), Some("1-0".to_string()));
+/// assert_eq!(get_tag(pgn, "FEN"), None);
+/// ```
+pub fn get_tag(pgn: &str, name: &str) -> Option<String> {
+    let prefix = format!("[{} \"", name);
+    pgn.lines()
+        .map(|line| line.trim())
+        .find(|line| line.starts_with(&prefix) && line.ends_with("\"]"))
+        .map(|line| line[prefix.len()..line.len() - 2].to_string())
+}
+
+/// Get the moves in the movetext of a PGN, in standard algebraic notation.
+/// Tags, comments, variations, move numbers,
+/// numeric annotation glyphs and the result are skipped
+fn get_sans(pgn: &str) -> Result<Vec<String>, PgnError> {
+    let mut movetext = String::new();
+    let mut comment_depth = 0;
+    let mut variation_depth = 0;
+    for line in pgn.lines() {
+        if comment_depth == 0 && line.trim_start().starts_with('[') {
+            continue
+        }
+        let mut is_line_comment = false;
+        for letter in line.chars() {
+            match letter {
+                _ if is_line_comment => {},
+                '}' if comment_depth > 0 => comment_depth = 0,
+                _ if comment_depth > 0 => {},
+                '{' => comment_depth = 1,
+                ';' => is_line_comment = true,
+                '(' => variation_depth += 1,
+                ')' if variation_depth > 0 => variation_depth -= 1,
+                _ if variation_depth > 0 => {},
+                _ => movetext.push(letter),
+            }
+        }
+        movetext.push(' ');
+    }
+    if comment_depth > 0 {
+        return Err(PgnError::UnclosedComment);
+    }
+    if variation_depth > 0 {
+        return Err(PgnError::UnclosedVariation);
+    }
+    let sans = movetext
+        .split_whitespace()
+        .filter(|token| !["1-0", "0-1", "1/2-1/2", "*"].contains(token))
+        .map(skip_move_number)
+        .filter(|token| !token.is_empty() && !token.starts_with('$'))
+        .map(|token| token.to_string())
+        .collect();
+    Ok(sans)
+}
+
+/// Skip the move number at the start of a token, e.g. '12.' or '12...',
+/// which may be written without a space before the move
+fn skip_move_number(token: &str) -> &str {
+    let without_digits = token.trim_start_matches(|c: char| c.is_ascii_digit());
+    match without_digits.starts_with('.') {
+        true => without_digits.trim_start_matches('.'),
+        false => token,
+    }
+}
+
+/// Join tokens with spaces, starting a new line before a line gets too long
+fn wrap_tokens(tokens: &[String]) -> String {
+    let mut text = String::new();
+    let mut line_length = 0;
+    for token in tokens {
+        if line_length > 0 && line_length + 1 + token.len() > MAX_LINE_LENGTH {
+            text.push('\n');
+            line_length = 0;
+        } else if line_length > 0 {
+            text.push(' ');
+            line_length += 1;
+        }
+        text.push_str(token);
+        line_length += token.len();
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess_move::Move;
+    use crate::square::Square;
+
+    fn do_moves(game: &mut Game, moves: Vec<(&str, &str)>) {
+        for (from, to) in moves {
+            game.do_move(&Move::new(Square::new(from), Square::new(to)));
+        }
+    }
+
+    #[test]
+    fn finished_game() {
+        let mut game = Game::new();
+        do_moves(&mut game, vec![("e2", "e3"), ("f7", "f6"), ("d1", "h5"), ("a7", "a6"), ("h5", "e8")]);
+        let pgn = to_pgn(&game, false);
+        assert!(pgn.starts_with("[Event \"Search And Destroy Chess\"]\n"));
+        assert!(pgn.contains("[Result \"1-0\"]\n\n"));
+        assert!(!pgn.contains("[FEN"));
+        assert!(pgn.ends_with("1. e3 f6 2. Qh5 a6 3. Qxe8 1-0\n"));
+    }
+    #[test]
+    fn fog_comments() {
+        let mut game = Game::new();
+        do_moves(&mut game, vec![("e2", "e4"), ("d7", "d5"), ("e4", "d5")]);
+        let pgn = to_pgn(&game, true);
+        assert!(pgn.contains("1. e4 {Sees nothing} 1... d5 {Sees nothing} 2. exd5 {Sees Pd5} *"));
+    }
+    #[test]
+    fn set_up_position() {
+        let position = crate::fen::position_from_fen("4k3/8/8/8/8/8/8/4K3 b - - 0 40").unwrap();
+        let mut game = Game::new();
+        game.set_position(position);
+        do_moves(&mut game, vec![("e8", "d8"), ("e1", "d1")]);
+        let pgn = to_pgn(&game, false);
+        assert!(pgn.contains("[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/8/4K3 b - - 0 40\"]\n"));
+        assert!(pgn.ends_with("40... Kd8 41. Kd1 *\n"));
+    }
+    #[test]
+    fn export_import_round_trip() {
+        let mut game = Game::new();
+        do_moves(&mut game, vec![("e2", "e4"), ("d7", "d5"), ("e4", "d5"), ("g8", "f6"), ("f1", "b5"), ("c7", "c6"), ("g1", "f3"), ("c6", "b5"), ("e1", "g1")]);
+        for with_fog_comments in [false, true] {
+            let replayed = game_from_pgn(&to_pgn(&game, with_fog_comments)).unwrap();
+            assert_eq!(replayed.get_position(), game.get_position());
+            assert_eq!(replayed.get_history(), game.get_history());
+        }
+    }
+    #[test]
+    fn import_from_fen_tag() {
+        let pgn = "[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/8/4K3 b - - 0 40\"]\n\n40... Kd8 41. Kd1 *\n";
+        let game = game_from_pgn(pgn).unwrap();
+        assert_eq!(crate::fen::position_to_fen(game.get_position()), "3k4/8/8/8/8/8/8/3K4 b - - 2 41");
+    }
+    #[test]
+    fn import_castling_with_zeroes() {
+        let game = game_from_pgn("1.Nf3 Nf6 2.g3 g6 3.Bg2 Bg7 4.0-0 0-0 *").unwrap();
+        assert_eq!(crate::fen::position_to_fen(game.get_position()), "rnbq1rk1/ppppppbp/5np1/8/8/5NP1/PPPPPPBP/RNBQ1RK1 w - - 4 5");
+    }
+    #[test]
+    fn import_at_ply_zero() {
+        let game = game_from_pgn_at_ply("1. e4 e5 *", 0).unwrap();
+        assert_eq!(game.get_position(), &Position::new());
+    }
+    #[test]
+    fn import_errors_cite_ply() {
+        assert_eq!(
+            game_from_pgn("1. e4 e5 2. Nf3 Nc6 3. Bb9 *"),
+            Err(PgnError::InvalidMove { ply: 5, error: crate::san::SanError::Malformed("Bb9".to_string()) })
+        );
+        assert_eq!(
+            game_from_pgn("1. e3 f6 2. Qh5 a6 3. Qxe8 a5 1-0"),
+            Err(PgnError::MoveAfterGameOver { ply: 6, san: "a5".to_string() })
+        );
+        assert_eq!(game_from_pgn_at_ply("1. e4 e5 *", 3), Err(PgnError::PlyOutOfRange { ply: 3, n_plies: 2 }));
+        assert_eq!(game_from_pgn("1. e4 {unclosed e5 *"), Err(PgnError::UnclosedComment));
+        assert_eq!(game_from_pgn("[FEN \"8/8 w - - 0 1\"]\n\n*").unwrap_err().to_string(), "Invalid FEN tag: A board has 8 ranks separated by '/', found 2");
+    }
+    #[test]
+    fn long_movetext_is_wrapped() {
+        let mut game = Game::new();
+        for _ in 0..10 {
+            do_moves(&mut game, vec![("g1", "f3"), ("g8", "f6"), ("f3", "g1"), ("f6", "g8")]);
+        }
+        let pgn = to_pgn(&game, false);
+        assert!(pgn.lines().all(|line| line.len() <= MAX_LINE_LENGTH));
+    }
+}
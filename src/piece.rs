@@ -9,6 +9,22 @@ pub struct Piece {
 }
 
 impl Piece {
+    /// Create a chesspiece
+    /// ```
+    /// use search_and_destroy_chess_2::color::Color;
+    /// use search_and_destroy_chess_2::piece::Piece;
+    /// use search_and_destroy_chess_2::piece::create_white_queen;
+    /// use search_and_destroy_chess_2::piece_type::PieceType;
+    /// 
+    /// let piece = Piece::new(Color::White, PieceType::Queen);
+    /// assert_eq!(piece, create_white_queen());
+    /// ```
+    pub fn new(color: Color, piece_type: PieceType) -> Piece {
+        Piece {
+            color,
+            r#type: piece_type,
+        }
+    }
     /// Clone a chesspiece
     /// ```
     /// use search_and_destroy_chess_2::piece::Piece;
@@ -19,6 +35,7 @@ impl Piece {
     /// 
     /// assert_eq!(piece, piece_again);
     /// ```
+    #[allow(clippy::should_implement_trait)]
     pub fn clone(&self) -> Piece {
         Piece {
             color: self.color,
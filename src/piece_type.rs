@@ -21,6 +21,69 @@ pub enum PieceType {
 }
 
 
+/// Get the types of pieces a pawn can promote to
+/// 
+/// ```
+/// use search_and_destroy_chess_2::piece_type::PieceType;
+/// use search_and_destroy_chess_2::piece_type::get_promotion_piece_types;
+/// 
+/// let piece_types = get_promotion_piece_types();
+/// assert_eq!(piece_types.len(), 4);
+/// assert!(!piece_types.contains(&PieceType::King));
+/// assert!(!piece_types.contains(&PieceType::Pawn));
+/// ```
+pub fn get_promotion_piece_types() -> Vec<PieceType> {
+    vec![
+        PieceType::Queen,
+        PieceType::Rook,
+        PieceType::Bishop,
+        PieceType::Knight,
+    ]
+}
+
+/// Get the piece type from its letter in algebraic notation,
+/// which is case-insensitive
+/// 
+/// ```
+/// use search_and_destroy_chess_2::piece_type::PieceType;
+/// use search_and_destroy_chess_2::piece_type::from_char;
+/// 
+/// assert_eq!(from_char('N'), Some(PieceType::Knight));
+/// assert_eq!(from_char('p'), Some(PieceType::Pawn));
+/// assert_eq!(from_char('x'), None);
+/// ```
+pub fn from_char(letter: char) -> Option<PieceType> {
+    match letter.to_ascii_uppercase() {
+        'B' => Some(PieceType::Bishop),
+        'K' => Some(PieceType::King),
+        'N' => Some(PieceType::Knight),
+        'P' => Some(PieceType::Pawn),
+        'Q' => Some(PieceType::Queen),
+        'R' => Some(PieceType::Rook),
+        _ => None,
+    }
+}
+
+/// Convert a PieceType to its uppercase letter in algebraic notation
+/// 
+/// ```
+/// use search_and_destroy_chess_2::piece_type::PieceType;
+/// use search_and_destroy_chess_2::piece_type::to_char;
+/// 
+/// assert_eq!(to_char(PieceType::Knight), 'N');
+/// assert_eq!(to_char(PieceType::Pawn), 'P');
+/// ```
+pub fn to_char(piece_type: PieceType) -> char {
+    match piece_type {
+        PieceType::Bishop => 'B',
+        PieceType::King => 'K',
+        PieceType::Knight => 'N',
+        PieceType::Pawn => 'P',
+        PieceType::Queen => 'Q',
+        PieceType::Rook => 'R',
+    }
+}
+
 /// Convert a PieceType to a String
 /// 
 /// ```
@@ -58,6 +121,7 @@ mod tests {
 
     }
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn clone() {
         let piece_type = PieceType::Bishop;
         let piece_type_clone = piece_type.clone();
@@ -65,6 +129,13 @@ mod tests {
 
     }
 
+    #[test]
+    fn char_round_trip() {
+        for piece_type in [PieceType::Bishop, PieceType::King, PieceType::Knight, PieceType::Pawn, PieceType::Queen, PieceType::Rook] {
+            assert_eq!(from_char(to_char(piece_type)), Some(piece_type));
+            assert_eq!(from_char(to_char(piece_type).to_ascii_lowercase()), Some(piece_type));
+        }
+    }
     #[test]
     fn bishop_to_str() {
         let str = to_str(PieceType::Bishop);
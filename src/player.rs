@@ -0,0 +1,147 @@
+use crate::chess_move::Move;
+use crate::color::Color;
+use crate::game::Game;
+use crate::observation::Observation;
+
+/// Someone or something that plays a game, by choosing moves.
+/// A player only gets what it can see through the fog of war,
+/// so it cannot cheat.
+/// A player can be sent to another thread, to think without blocking a window
+pub trait Player: Send {
+
+    /// Choose one of the moves of the observation.
+    /// Returns None if the player gives up, e.g. as there is no move to do
+    fn choose_move(&mut self, observation: &Observation) -> Option<Move>;
+
+    /// Get the name of the player, e.g. 'random'
+    fn get_name(&self) -> String;
+}
+
+/// The computer players of a game, one per color,
+/// where a color without a player is played by a human.
+/// Players are told apart by their names
+#[derive(Default)]
+pub struct Players {
+    players: [Option<Box<dyn Player>>; 2],
+}
+
+impl Players {
+
+    /// Create the players of a game in which humans play both colors
+    pub fn new() -> Players {
+        Players { players: [None, None] }
+    }
+
+    /// Get the player of a color, if it is not played by a human
+    pub fn get(&self, color: Color) -> Option<&dyn Player> { self.players[get_index(color)].as_deref() }
+
+    /// Set the player of a color, where None is for a human
+    pub fn set(&mut self, color: Color, player: Option<Box<dyn Player>>) { self.players[get_index(color)] = player }
+
+    /// Take the player of a color, after which the color has no player
+    pub fn take(&mut self, color: Color) -> Option<Box<dyn Player>> { self.players[get_index(color)].take() }
+}
+
+impl std::fmt::Debug for Players {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_list().entries(self.players.iter().map(|player| player.as_ref().map(|player| player.get_name()))).finish()
+    }
+}
+
+impl PartialEq for Players {
+    fn eq(&self, other: &Self) -> bool {
+        let get_names = |players: &Players| players.players.iter()
+            .map(|player| player.as_ref().map(|player| player.get_name()))
+            .collect::<Vec<_>>();
+        get_names(self) == get_names(other)
+    }
+}
+
+impl Eq for Players {}
+
+/// Get the index of the player of a color
+fn get_index(color: Color) -> usize {
+    match color {
+        Color::White => 0,
+        Color::Black => 1,
+    }
+}
+
+/// Let two players play a game from the start position,
+/// until the game is over, a player does not choose a move,
+/// or a maximum number of plies is done
+///
+/// ```
+/// use search_and_destroy_chess_2::player::play_game;
+/// use search_and_destroy_chess_2::random_player::RandomPlayer;
+///
+/// let game = play_game(&mut RandomPlayer::new(), &mut RandomPlayer::new(), 10);
+/// assert!(game.get_history().len() <= 10);
+/// ```
+pub fn play_game(white: &mut dyn Player, black: &mut dyn Player, max_plies: usize) -> Game {
+    let mut game = Game::new();
+    while !crate::game_status::is_over(game.get_status()) && game.get_history().len() < max_plies {
+        let player: &mut dyn Player = match game.get_current_player() {
+            Color::White => &mut *white,
+            Color::Black => &mut *black,
+        };
+        if !game.play_turn(player) {
+            break;
+        }
+    }
+    game
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Let a player choose a move in the position of a FEN string
+    fn choose_move(player: &mut dyn Player, fen: &str) -> String {
+        let mut game = Game::new();
+        game.set_position(crate::fen::position_from_fen(fen).unwrap());
+        crate::chess_move::to_str(&player.choose_move(&game.get_observation()).unwrap())
+    }
+
+    /// Create the computer players that search for the best move, without blundering
+    fn create_searching_players() -> Vec<Box<dyn Player>> {
+        let mut alpha_beta = crate::alpha_beta::AlphaBetaPlayer::from_seed(crate::alpha_beta::Difficulty::Normal, 42);
+        alpha_beta.set_blunder_rate(0.0);
+        vec![Box::new(alpha_beta), Box::new(crate::ismcts::IsmctsPlayer::from_seed(200, 42))]
+    }
+
+    /// Create the computer players that search until a time limit of 50 ms is reached
+    fn create_timed_players() -> Vec<Box<dyn Player>> {
+        let time_limit = Some(std::time::Duration::from_millis(50));
+        let mut alpha_beta = crate::alpha_beta::AlphaBetaPlayer::from_seed(crate::alpha_beta::Difficulty::Easy, 1);
+        alpha_beta.set_samples(usize::MAX);
+        alpha_beta.set_time_limit(time_limit);
+        let mut ismcts = crate::ismcts::IsmctsPlayer::from_seed(usize::MAX, 1);
+        ismcts.set_time_limit(time_limit);
+        vec![Box::new(alpha_beta), Box::new(ismcts)]
+    }
+
+    #[test]
+    fn searching_players_capture_the_king() {
+        for mut player in create_searching_players() {
+            assert_eq!(choose_move(player.as_mut(), "4k3/8/8/8/8/8/8/4RK2 w - - 0 1"), "e1e8", "{}", player.get_name());
+            assert_eq!(choose_move(player.as_mut(), "4k3/8/8/8/8/8/4n3/6K1 b - - 0 1"), "e2g1", "{}", player.get_name());
+        }
+    }
+    #[test]
+    fn timed_players_stop_at_the_time_limit() {
+        for mut player in create_timed_players() {
+            let start = std::time::Instant::now();
+            assert!(player.choose_move(&Game::new().get_observation()).is_some());
+            assert!(start.elapsed() < std::time::Duration::from_secs(5), "{}", player.get_name());
+        }
+    }
+    #[test]
+    fn searching_players_play_a_game() {
+        for (seed, mut player) in create_searching_players().into_iter().enumerate() {
+            let mut enemy = crate::random_player::RandomPlayer::from_seed(seed as u64);
+            let game = play_game(player.as_mut(), &mut enemy, 40);
+            assert!(game.get_history().len() >= 2, "{}", player.get_name());
+        }
+    }
+}
@@ -0,0 +1,410 @@
+use crate::board::Board;
+use crate::board::get_piece_at_square;
+use crate::castling_rights::CastlingRights;
+use crate::chess_move::Move;
+use crate::color::Color;
+use crate::piece::Piece;
+use crate::piece_type::PieceType;
+use crate::square::Square;
+use crate::square::get_nth_file;
+use crate::square::get_nth_rank;
+
+/// A position in a game: the pieces on the board,
+/// together with the temporary information that a Board does not have,
+/// such as whose turn it is, the castling rights, the en-passant target
+/// and the move clocks
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Position {
+    board: Board,
+    side_to_move: Color,
+    castling_rights: CastlingRights,
+    en_passant: Option<Square>,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+}
+
+impl Position {
+
+    /// Create the starting position of a game
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::board::Board;
+    /// use search_and_destroy_chess_2::castling_rights::CastlingRights;
+    /// use search_and_destroy_chess_2::color::Color;
+    /// use search_and_destroy_chess_2::position::Position;
+    ///
+    /// let position = Position::new();
+    /// assert_eq!(position.get_board(), &Board::new());
+    /// assert_eq!(position.get_side_to_move(), Color::White);
+    /// assert_eq!(position.get_castling_rights(), CastlingRights::new());
+    /// ```
+    pub fn new() -> Position {
+        Position {
+            board: Board::new(),
+            side_to_move: Color::White,
+            castling_rights: CastlingRights::new(),
+            en_passant: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+        }
+    }
+
+    /// Create a position from a board, whose turn it is and the castling rights.
+    /// There is no en-passant target and the clocks are those of the first move
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::board::Board;
+    /// use search_and_destroy_chess_2::castling_rights::CastlingRights;
+    /// use search_and_destroy_chess_2::color::Color;
+    /// use search_and_destroy_chess_2::position::Position;
+    ///
+    /// let position = Position::from_board(Board::new(), Color::Black, CastlingRights::none());
+    /// assert_eq!(position.get_side_to_move(), Color::Black);
+    /// ```
+    pub fn from_board(board: Board, side_to_move: Color, castling_rights: CastlingRights) -> Position {
+        Position {
+            board,
+            side_to_move,
+            castling_rights,
+            en_passant: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+        }
+    }
+
+    /// Do a move, returning the piece captured, if any.
+    /// When a king castles, its rook is moved as well.
+    /// When a pawn captures en passant, the pawn passed is removed.
+    /// A pawn that moves to the last rank must promote.
+    /// After the move, it is the other player's turn.
+    /// The halfmove clock is reset by a pawn move or a capture,
+    /// the fullmove number increases after Black has moved.
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::chess_move::Move;
+    /// use search_and_destroy_chess_2::color::Color;
+    /// use search_and_destroy_chess_2::position::Position;
+    /// use search_and_destroy_chess_2::square::Square;
+    ///
+    /// let mut position = Position::new();
+    /// position.apply_move(&Move::new(Square::new("e2"), Square::new("e4")));
+    /// assert_eq!(position.get_side_to_move(), Color::Black);
+    /// ```
+    pub fn apply_move(&mut self, chess_move: &Move) -> Option<Piece> {
+        let from = chess_move.get_from();
+        let to = chess_move.get_to();
+        if is_promotion(&self.board, chess_move) {
+            match chess_move.get_promotion() {
+                Some(piece_type) => assert!(
+                    crate::piece_type::get_promotion_piece_types().contains(&piece_type),
+                    "A pawn cannot promote to a {}", crate::piece_type::to_str(piece_type)
+                ),
+                None => panic!("A pawn moving to the last rank must promote"),
+            }
+        }
+        let is_castling = is_castling(&self.board, chess_move);
+        let is_en_passant = is_en_passant(&self.board, &self.en_passant, chess_move);
+        let is_double_push = is_double_pawn_push(&self.board, chess_move);
+        let is_pawn_move = crate::board::is_pawn(&self.board, from);
+        let mut captured = self.board.apply_move(chess_move);
+        if is_en_passant {
+            let passed_square = create_square(get_nth_file(&to).get(), get_nth_rank(&from) as u8);
+            captured = get_piece_at_square(&self.board, &passed_square);
+            self.board.set_piece_at_square(&passed_square, None);
+        }
+        self.en_passant = match is_double_push {
+            true => Some(create_square(get_nth_file(&from).get(), ((get_nth_rank(&from) + get_nth_rank(&to)) / 2) as u8)),
+            false => None,
+        };
+        if is_castling {
+            let rank_index = get_nth_rank(&to) as u8;
+            let (rook_from_file, rook_to_file) = match get_nth_file(&to).get() {
+                6 => (7, 5),
+                _ => (0, 3),
+            };
+            let rook_move = Move::new(
+                create_square(rook_from_file, rank_index),
+                create_square(rook_to_file, rank_index)
+            );
+            self.board.apply_move(&rook_move);
+        }
+        self.castling_rights.remove_for_square(&from);
+        self.castling_rights.remove_for_square(&to);
+        self.halfmove_clock = match is_pawn_move || captured.is_some() {
+            true => 0,
+            false => self.halfmove_clock + 1,
+        };
+        if self.side_to_move == Color::Black {
+            self.fullmove_number += 1;
+        }
+        self.side_to_move = crate::color::get_other_color(self.side_to_move);
+        captured
+    }
+
+    /// Get the pieces on the board
+    pub fn get_board(&self) -> &Board { &self.board }
+
+    /// Get the castling rights of both players
+    pub fn get_castling_rights(&self) -> CastlingRights { self.castling_rights }
+
+    /// Get the square a pawn has just passed by moving two squares forward, if any.
+    /// An enemy pawn can capture en passant by moving to this square
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::chess_move::Move;
+    /// use search_and_destroy_chess_2::position::Position;
+    /// use search_and_destroy_chess_2::square::Square;
+    ///
+    /// let mut position = Position::new();
+    /// assert_eq!(position.get_en_passant(), None);
+    /// position.apply_move(&Move::new(Square::new("e2"), Square::new("e4")));
+    /// assert_eq!(position.get_en_passant(), Some(Square::new("e3")));
+    /// position.apply_move(&Move::new(Square::new("g8"), Square::new("f6")));
+    /// assert_eq!(position.get_en_passant(), None);
+    /// ```
+    pub fn get_en_passant(&self) -> Option<Square> { self.en_passant }
+
+    /// Get the number of the current move, which starts at 1
+    /// and increases after Black has moved
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::chess_move::Move;
+    /// use search_and_destroy_chess_2::position::Position;
+    /// use search_and_destroy_chess_2::square::Square;
+    ///
+    /// let mut position = Position::new();
+    /// assert_eq!(position.get_fullmove_number(), 1);
+    /// position.apply_move(&Move::new(Square::new("e2"), Square::new("e4")));
+    /// assert_eq!(position.get_fullmove_number(), 1);
+    /// position.apply_move(&Move::new(Square::new("e7"), Square::new("e5")));
+    /// assert_eq!(position.get_fullmove_number(), 2);
+    /// ```
+    pub fn get_fullmove_number(&self) -> u32 { self.fullmove_number }
+
+    /// Get the number of halfmoves since the last pawn move or capture
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::chess_move::Move;
+    /// use search_and_destroy_chess_2::position::Position;
+    /// use search_and_destroy_chess_2::square::Square;
+    ///
+    /// let mut position = Position::new();
+    /// position.apply_move(&Move::new(Square::new("g1"), Square::new("f3")));
+    /// assert_eq!(position.get_halfmove_clock(), 1);
+    /// position.apply_move(&Move::new(Square::new("e7"), Square::new("e5")));
+    /// assert_eq!(position.get_halfmove_clock(), 0);
+    /// ```
+    pub fn get_halfmove_clock(&self) -> u32 { self.halfmove_clock }
+
+    /// Get all the moves the player to move can do,
+    /// without considering if this leaves its king under attack.
+    /// This includes castling and capturing en passant.
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::position::Position;
+    /// use search_and_destroy_chess_2::rules::Rules;
+    ///
+    /// let position = Position::new();
+    /// assert_eq!(position.get_pseudo_legal_moves(&Rules::new()).len(), 20);
+    /// ```
+    pub fn get_pseudo_legal_moves(&self, rules: &crate::rules::Rules) -> Vec<Move> {
+        let mut moves = crate::move_generator::get_pseudo_legal_moves(&self.board, self.side_to_move);
+        moves.append(&mut crate::move_generator::get_castling_moves(self, rules));
+        moves.append(&mut crate::move_generator::get_en_passant_moves(self));
+        moves
+    }
+
+    /// Get the player whose turn it is
+    pub fn get_side_to_move(&self) -> Color { self.side_to_move }
+
+    /// Set the square a pawn has just passed by moving two squares forward
+    pub fn set_en_passant(&mut self, en_passant: Option<Square>) { self.en_passant = en_passant }
+
+    /// Set the number of the current move
+    pub fn set_fullmove_number(&mut self, fullmove_number: u32) { self.fullmove_number = fullmove_number }
+
+    /// Set the number of halfmoves since the last pawn move or capture
+    pub fn set_halfmove_clock(&mut self, halfmove_clock: u32) { self.halfmove_clock = halfmove_clock }
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Create a square from its file and rank indices
+fn create_square(file_index: usize, rank_index: u8) -> Square {
+    Square::from_indices(file_index, rank_index as usize)
+}
+
+/// Detect if a move on a board is a king castling,
+/// i.e. a king that moves two files
+///
+/// ```
+/// use search_and_destroy_chess_2::board::Board;
+/// use search_and_destroy_chess_2::chess_move::Move;
+/// use search_and_destroy_chess_2::position::is_castling;
+/// use search_and_destroy_chess_2::square::Square;
+///
+/// let board = Board::new();
+/// assert!(is_castling(&board, &Move::new(Square::new("e1"), Square::new("g1"))));
+/// assert!(!is_castling(&board, &Move::new(Square::new("e1"), Square::new("f1"))));
+/// ```
+pub fn is_castling(board: &Board, chess_move: &Move) -> bool {
+    match get_piece_at_square(board, &chess_move.get_from()) {
+        Some(piece) => {
+            let from_file = get_nth_file(&chess_move.get_from()).get() as i32;
+            let to_file = get_nth_file(&chess_move.get_to()).get() as i32;
+            piece.get_type() == PieceType::King && (from_file - to_file).abs() == 2
+        },
+        None => false,
+    }
+}
+
+/// Detect if a move on a board is a pawn moving to the last rank,
+/// which means it must promote
+///
+/// ```
+/// use search_and_destroy_chess_2::board::Board;
+/// use search_and_destroy_chess_2::chess_move::Move;
+/// use search_and_destroy_chess_2::position::is_promotion;
+/// use search_and_destroy_chess_2::square::Square;
+///
+/// let board = Board::new();
+/// assert!(is_promotion(&board, &Move::new(Square::new("a2"), Square::new("a8"))));
+/// assert!(is_promotion(&board, &Move::new(Square::new("a7"), Square::new("a1"))));
+/// assert!(!is_promotion(&board, &Move::new(Square::new("a2"), Square::new("a3"))));
+/// assert!(!is_promotion(&board, &Move::new(Square::new("a1"), Square::new("a8"))));
+/// ```
+pub fn is_promotion(board: &Board, chess_move: &Move) -> bool {
+    match get_piece_at_square(board, &chess_move.get_from()) {
+        Some(piece) => {
+            let last_rank_index = match piece.get_color() {
+                Color::White => 7,
+                Color::Black => 0,
+            };
+            piece.get_type() == PieceType::Pawn && get_nth_rank(&chess_move.get_to()) == last_rank_index
+        },
+        None => false,
+    }
+}
+
+/// Detect if a move on a board is a pawn moving two squares forward
+fn is_double_pawn_push(board: &Board, chess_move: &Move) -> bool {
+    match get_piece_at_square(board, &chess_move.get_from()) {
+        Some(piece) => {
+            let from_rank = get_nth_rank(&chess_move.get_from()) as i32;
+            let to_rank = get_nth_rank(&chess_move.get_to()) as i32;
+            piece.get_type() == PieceType::Pawn && (from_rank - to_rank).abs() == 2
+        },
+        None => false,
+    }
+}
+
+/// Detect if a move on a board is a pawn capturing en passant,
+/// i.e. a pawn moving to the en-passant target square
+///
+/// ```
+/// use search_and_destroy_chess_2::board::Board;
+/// use search_and_destroy_chess_2::chess_move::Move;
+/// use search_and_destroy_chess_2::position::is_en_passant;
+/// use search_and_destroy_chess_2::square::Square;
+///
+/// let board = Board::new();
+/// let chess_move = Move::new(Square::new("d2"), Square::new("e3"));
+/// assert!(is_en_passant(&board, &Some(Square::new("e3")), &chess_move));
+/// assert!(!is_en_passant(&board, &None, &chess_move));
+/// ```
+pub fn is_en_passant(board: &Board, en_passant: &Option<Square>, chess_move: &Move) -> bool {
+    match (get_piece_at_square(board, &chess_move.get_from()), en_passant) {
+        (Some(piece), Some(target)) => piece.get_type() == PieceType::Pawn && chess_move.get_to() == *target,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn do_moves(position: &mut Position, moves: Vec<(&str, &str)>) {
+        for (from, to) in moves {
+            position.apply_move(&Move::new(Square::new(from), Square::new(to)));
+        }
+    }
+
+    #[test]
+    fn castle_king_side() {
+        let mut position = Position::new();
+        do_moves(&mut position, vec![("g1", "f3"), ("g8", "f6"), ("g2", "g3"), ("g7", "g6"), ("f1", "g2"), ("f8", "g7"), ("e1", "g1")]);
+        assert_eq!(get_piece_at_square(position.get_board(), &Square::new("g1")), Some(crate::piece::create_white_king()));
+        assert_eq!(get_piece_at_square(position.get_board(), &Square::new("f1")), Some(crate::piece::create_white_rook()));
+        assert_eq!(get_piece_at_square(position.get_board(), &Square::new("h1")), None);
+        assert!(!position.get_castling_rights().can_castle_king_side(Color::White));
+        assert!(!position.get_castling_rights().can_castle_queen_side(Color::White));
+        assert!(position.get_castling_rights().can_castle_king_side(Color::Black));
+    }
+    #[test]
+    fn castle_queen_side() {
+        let mut position = Position::new();
+        do_moves(&mut position, vec![("e2", "e3"), ("b8", "c6"), ("a2", "a3"), ("d7", "d6"), ("a3", "a4"), ("c8", "e6"), ("a4", "a5"), ("d8", "d7"), ("a5", "a6"), ("e8", "c8")]);
+        assert_eq!(get_piece_at_square(position.get_board(), &Square::new("c8")), Some(crate::piece::create_black_king()));
+        assert_eq!(get_piece_at_square(position.get_board(), &Square::new("d8")), Some(crate::piece::create_black_rook()));
+        assert_eq!(get_piece_at_square(position.get_board(), &Square::new("a8")), None);
+        assert_eq!(position.get_side_to_move(), Color::White);
+    }
+    #[test]
+    fn capture_en_passant() {
+        let mut position = Position::new();
+        do_moves(&mut position, vec![("e2", "e4"), ("a7", "a6"), ("e4", "e5"), ("d7", "d5")]);
+        assert_eq!(position.get_en_passant(), Some(Square::new("d6")));
+        let en_passant_moves = crate::move_generator::get_en_passant_moves(&position);
+        assert_eq!(en_passant_moves.len(), 1);
+        assert_eq!(en_passant_moves[0].get_captured(), Some(crate::piece::create_black_pawn()));
+        assert!(position.get_pseudo_legal_moves(&crate::rules::Rules::new()).contains(&en_passant_moves[0]));
+        let captured = position.apply_move(&en_passant_moves[0]);
+        assert_eq!(captured, Some(crate::piece::create_black_pawn()));
+        assert_eq!(get_piece_at_square(position.get_board(), &Square::new("d5")), None);
+        assert_eq!(get_piece_at_square(position.get_board(), &Square::new("d6")), Some(crate::piece::create_white_pawn()));
+        assert_eq!(position.get_en_passant(), None);
+    }
+    #[test]
+    fn en_passant_expires() {
+        let mut position = Position::new();
+        do_moves(&mut position, vec![("e2", "e4"), ("a7", "a6"), ("e4", "e5"), ("d7", "d5"), ("h2", "h3"), ("h7", "h6")]);
+        assert_eq!(position.get_en_passant(), None);
+        assert!(crate::move_generator::get_en_passant_moves(&position).is_empty());
+    }
+    #[test]
+    fn promote_to_knight() {
+        let mut position = Position::new();
+        do_moves(&mut position, vec![("h2", "h4"), ("g7", "g5"), ("h4", "g5"), ("f8", "g7"), ("g5", "g6"), ("g8", "f6"), ("g6", "g7"), ("a7", "a6")]);
+        let mut chess_move = Move::new(Square::new("g7"), Square::new("h8"));
+        chess_move.set_promotion(Some(PieceType::Knight));
+        let captured = position.apply_move(&chess_move);
+        assert_eq!(captured, Some(crate::piece::create_black_rook()));
+        assert_eq!(get_piece_at_square(position.get_board(), &Square::new("h8")), Some(crate::piece::create_white_knight()));
+    }
+    #[test]
+    #[should_panic(expected = "A pawn moving to the last rank must promote")]
+    fn pawn_on_last_rank_must_promote() {
+        let mut position = Position::new();
+        do_moves(&mut position, vec![("h2", "h4"), ("g7", "g5"), ("h4", "g5"), ("f8", "g7"), ("g5", "g6"), ("g8", "f6"), ("g6", "g7"), ("a7", "a6"), ("g7", "h8")]);
+    }
+    #[test]
+    #[should_panic(expected = "A pawn cannot promote to a King")]
+    fn pawn_cannot_promote_to_king() {
+        let mut position = Position::new();
+        do_moves(&mut position, vec![("h2", "h4"), ("g7", "g5"), ("h4", "g5"), ("f8", "g7"), ("g5", "g6"), ("g8", "f6"), ("g6", "g7"), ("a7", "a6")]);
+        let mut chess_move = Move::new(Square::new("g7"), Square::new("h8"));
+        chess_move.set_promotion(Some(PieceType::King));
+        position.apply_move(&chess_move);
+    }
+    #[test]
+    fn capturing_rook_removes_castling_right() {
+        let mut position = Position::new();
+        do_moves(&mut position, vec![("g2", "g3"), ("b7", "b6"), ("f1", "g2"), ("e7", "e6"), ("g2", "a8")]);
+        assert!(!position.get_castling_rights().can_castle_queen_side(Color::Black));
+        assert!(position.get_castling_rights().can_castle_king_side(Color::Black));
+    }
+}
@@ -0,0 +1,73 @@
+use crate::chess_move::Move;
+use crate::observation::Observation;
+use rand::SeedableRng;
+use rand::seq::SliceRandom;
+
+/// A player that does a random move
+pub struct RandomPlayer {
+    rng: rand::rngs::StdRng,
+}
+
+impl RandomPlayer {
+
+    /// Create a player that does a different random move each game
+    pub fn new() -> RandomPlayer {
+        RandomPlayer {
+            rng: rand::rngs::StdRng::from_entropy(),
+        }
+    }
+
+    /// Create a player that does the same random moves for the same seed,
+    /// e.g. to replay a game
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::player::play_game;
+    /// use search_and_destroy_chess_2::random_player::RandomPlayer;
+    ///
+    /// let game_1 = play_game(&mut RandomPlayer::from_seed(1), &mut RandomPlayer::from_seed(2), 20);
+    /// let game_2 = play_game(&mut RandomPlayer::from_seed(1), &mut RandomPlayer::from_seed(2), 20);
+    /// assert_eq!(game_1.get_history(), game_2.get_history());
+    /// ```
+    pub fn from_seed(seed: u64) -> RandomPlayer {
+        RandomPlayer {
+            rng: rand::rngs::StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Default for RandomPlayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl crate::player::Player for RandomPlayer {
+    fn choose_move(&mut self, observation: &Observation) -> Option<Move> {
+        observation.get_moves().choose(&mut self.rng).cloned()
+    }
+
+    fn get_name(&self) -> String { "random".to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::Player;
+
+    #[test]
+    fn chooses_one_of_the_moves() {
+        let observation = Observation::new(&crate::position::Position::new(), &crate::rules::Rules::new());
+        let mut player = RandomPlayer::from_seed(42);
+        for _ in 0..10 {
+            let chess_move = player.choose_move(&observation).unwrap();
+            assert!(observation.get_moves().contains(&chess_move));
+        }
+    }
+    #[test]
+    fn random_games_end_or_stop() {
+        for seed in 0..5 {
+            let game = crate::player::play_game(&mut RandomPlayer::from_seed(seed), &mut RandomPlayer::from_seed(seed + 100), 200);
+            assert!(crate::game_status::is_over(game.get_status()) || game.get_history().len() == 200);
+        }
+    }
+}
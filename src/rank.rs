@@ -65,6 +65,21 @@ impl Rank {
     /// assert_eq!(piece, None);
     /// ```
     pub fn get(&self) -> &Vec<Option<Piece>> { &self.pieces }
+
+    /// Put a piece at a file, or remove it by putting nothing there
+    /// ```
+    /// use search_and_destroy_chess_2::rank::Rank;
+    /// use search_and_destroy_chess_2::rank::get_piece;
+    /// use search_and_destroy_chess_2::file_index::FileIndex;
+    /// use search_and_destroy_chess_2::piece::create_black_king;
+    /// 
+    /// let mut rank = Rank::new(vec![None; 8]);
+    /// rank.set(&FileIndex::new(4), Some(create_black_king()));
+    /// assert_eq!(get_piece(&rank, &FileIndex::new(4)), Some(create_black_king()));
+    /// ```
+    pub fn set(&mut self, file_index: &FileIndex, piece: Option<Piece>) {
+        self.pieces[file_index.get()] = piece;
+    }
 }
 
 pub fn get_piece(rank: &Rank, file_index: &FileIndex) -> Option<Piece> {
@@ -125,4 +140,12 @@ mod tests {
         let rank = Rank::new(vec![None; 8]);
         assert_eq!(rank.get().len(), 8);
     }
+    #[test]
+    fn set_and_remove_piece() {
+        let mut rank = Rank::new(create_pieces_from_rank_index(0));
+        rank.set(&FileIndex::new(0), None);
+        assert_eq!(get_piece(&rank, &FileIndex::new(0)), None);
+        rank.set(&FileIndex::new(0), Some(crate::piece::create_black_pawn()));
+        assert_eq!(get_piece(&rank, &FileIndex::new(0)), Some(crate::piece::create_black_pawn()));
+    }
 }
\ No newline at end of file
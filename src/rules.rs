@@ -0,0 +1,45 @@
+/// The rules of a game that can be changed.
+///
+/// Under fog of war, a player cannot see which squares the other player attacks.
+/// Therefore, by default, a king may castle out of, through or into an attacked square.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Rules {
+    castling_through_attack: bool,
+}
+
+impl Rules {
+
+    /// Create the default rules of Search And Destroy Chess
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::rules::Rules;
+    ///
+    /// let rules = Rules::new();
+    /// assert!(rules.is_castling_through_attack_allowed());
+    /// ```
+    pub fn new() -> Rules {
+        Rules {
+            castling_through_attack: true,
+        }
+    }
+
+    /// Can a king castle out of, through or into a square that is attacked?
+    pub fn is_castling_through_attack_allowed(&self) -> bool { self.castling_through_attack }
+
+    /// Set if a king can castle out of, through or into a square that is attacked
+    ///
+    /// ```
+    /// use search_and_destroy_chess_2::rules::Rules;
+    ///
+    /// let mut rules = Rules::new();
+    /// rules.set_castling_through_attack_allowed(false);
+    /// assert!(!rules.is_castling_through_attack_allowed());
+    /// ```
+    pub fn set_castling_through_attack_allowed(&mut self, is_allowed: bool) { self.castling_through_attack = is_allowed }
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,284 @@
+use crate::board::get_piece_at_square;
+use crate::chess_move::Move;
+use crate::piece_type::PieceType;
+use crate::position::Position;
+use crate::square::get_nth_file;
+use crate::square::get_nth_rank;
+
+/// The reasons why a move in standard algebraic notation (SAN)
+/// cannot be done in a position
+///
+/// ```
+/// use search_and_destroy_chess_2::san::SanError;
+///
+/// let error = SanError::Ambiguous("Nd2".to_string());
+/// assert_eq!(error.to_string(), "Move 'Nd2' can be done by more than one piece");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SanError {
+    Malformed(String),
+    NoSuchMove(String),
+    Ambiguous(String),
+}
+
+impl std::fmt::Display for SanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SanError::Malformed(san) => write!(f, "Move '{}' is not in standard algebraic notation", san),
+            SanError::NoSuchMove(san) => write!(f, "Move '{}' cannot be done in this position", san),
+            SanError::Ambiguous(san) => write!(f, "Move '{}' can be done by more than one piece", san),
+        }
+    }
+}
+
+impl std::error::Error for SanError {}
+
+/// Get the move in a position from its standard algebraic notation (SAN).
+/// Check and annotation symbols, such as '+', '#', '!' and '?', are ignored,
+/// and castling can be written with zeroes.
+/// A capture does not need an 'x', but the piece to move must be unambiguous
+///
+/// ```
+/// use search_and_destroy_chess_2::chess_move::Move;
+/// use search_and_destroy_chess_2::position::Position;
+/// use search_and_destroy_chess_2::rules::Rules;
+/// use search_and_destroy_chess_2::san::SanError;
+/// use search_and_destroy_chess_2::san::get_move_from_san;
+/// use search_and_destroy_chess_2::square::Square;
+///
+/// let position = Position::new();
+/// let chess_move = get_move_from_san(&position, &Rules::new(), "Nf3").unwrap();
+/// assert_eq!(chess_move, Move::new(Square::new("g1"), Square::new("f3")));
+/// assert_eq!(get_move_from_san(&position, &Rules::new(), "e5"), Err(SanError::NoSuchMove("e5".to_string())));
+/// assert_eq!(get_move_from_san(&position, &Rules::new(), "Zz9"), Err(SanError::Malformed("Zz9".to_string())));
+/// ```
+pub fn get_move_from_san(position: &Position, rules: &crate::rules::Rules, san: &str) -> Result<Move, SanError> {
+    let board = position.get_board();
+    let stripped = san.trim_end_matches(|c| "+#!?".contains(c));
+    let moves = position.get_pseudo_legal_moves(rules);
+    let castling_file = match stripped {
+        "O-O" | "0-0" => Some(6),
+        "O-O-O" | "0-0-0" => Some(2),
+        _ => None,
+    };
+    let candidates: Vec<Move> = match castling_file {
+        Some(file) => moves
+            .into_iter()
+            .filter(|chess_move| crate::position::is_castling(board, chess_move) && get_nth_file(&chess_move.get_to()).get() == file)
+            .collect(),
+        None => {
+            let captures = match get_san_regex().captures(stripped) {
+                Some(captures) => captures,
+                None => return Err(SanError::Malformed(san.to_string())),
+            };
+            let piece_type = match captures.get(1) {
+                Some(letter) => crate::piece_type::from_char(letter.as_str().chars().next().unwrap()).unwrap(),
+                None => PieceType::Pawn,
+            };
+            let from_file = captures.get(2).map(|file| file.as_str().to_string());
+            let from_rank = captures.get(3).map(|rank| rank.as_str().parse::<usize>().unwrap());
+            let to = crate::square::Square::new(captures.get(4).unwrap().as_str());
+            let promotion = captures.get(6).map(|letter| crate::piece_type::from_char(letter.as_str().chars().next().unwrap()).unwrap());
+            moves
+                .into_iter()
+                .filter(|chess_move| {
+                    let from = chess_move.get_from();
+                    let is_piece_type = match get_piece_at_square(board, &from) {
+                        Some(piece) => piece.get_type() == piece_type,
+                        None => false,
+                    };
+                    is_piece_type
+                        && chess_move.get_to() == to
+                        && chess_move.get_promotion() == promotion
+                        && from_file.as_ref().is_none_or(|file| from.get_file() == *file)
+                        && from_rank.is_none_or(|rank| from.get_rank() == rank)
+                        && !crate::position::is_castling(board, chess_move)
+                })
+                .collect()
+        },
+    };
+    match candidates.len() {
+        0 => Err(SanError::NoSuchMove(san.to_string())),
+        1 => Ok(candidates[0].clone()),
+        _ => Err(SanError::Ambiguous(san.to_string())),
+    }
+}
+
+/// Get the standard algebraic notation (SAN) of a move in a position,
+/// e.g. 'e4', 'Nbd7', 'exd6', 'e8=Q' or 'O-O'.
+///
+/// As a player wins by capturing the king, there is no check or checkmate,
+/// so SAN in this game never ends with '+' or '#'.
+/// A piece is disambiguated against the other moves it could do,
+/// because all of these are valid moves in this game.
+///
+/// ```
+/// use search_and_destroy_chess_2::chess_move::Move;
+/// use search_and_destroy_chess_2::position::Position;
+/// use search_and_destroy_chess_2::san::get_san;
+/// use search_and_destroy_chess_2::square::Square;
+///
+/// let position = Position::new();
+/// assert_eq!(get_san(&position, &Move::new(Square::new("e2"), Square::new("e4"))), "e4");
+/// assert_eq!(get_san(&position, &Move::new(Square::new("g1"), Square::new("f3"))), "Nf3");
+/// ```
+pub fn get_san(position: &Position, chess_move: &Move) -> String {
+    let board = position.get_board();
+    let from = chess_move.get_from();
+    let to = chess_move.get_to();
+    let piece = match get_piece_at_square(board, &from) {
+        Some(piece) => piece,
+        None => panic!("There is no piece to move at {}", from.get()),
+    };
+    if crate::position::is_castling(board, chess_move) {
+        return match get_nth_file(&to).get() {
+            6 => "O-O".to_string(),
+            _ => "O-O-O".to_string(),
+        }
+    }
+    let is_capture = get_piece_at_square(board, &to).is_some()
+        || crate::position::is_en_passant(board, &position.get_en_passant(), chess_move);
+    let mut san = String::new();
+    if piece.get_type() == PieceType::Pawn {
+        if is_capture {
+            san.push_str(&from.get_file());
+        }
+    } else {
+        san.push(crate::piece_type::to_char(piece.get_type()));
+        san.push_str(&get_disambiguation(position, chess_move, piece.get_type()));
+    }
+    if is_capture {
+        san.push('x');
+    }
+    san.push_str(&to.get());
+    if let Some(piece_type) = chess_move.get_promotion() {
+        san.push('=');
+        san.push(crate::piece_type::to_char(piece_type));
+    }
+    san
+}
+
+/// Get the file, rank or square of the 'from' square a piece needs
+/// to tell it apart from other pieces of the same type that can move to the same square.
+/// * if no other piece can: nothing
+/// * if no other piece is on the same file: the file
+/// * if no other piece is on the same rank: the rank
+/// * else: the square
+fn get_disambiguation(position: &Position, chess_move: &Move, piece_type: PieceType) -> String {
+    let board = position.get_board();
+    let from = chess_move.get_from();
+    let others: Vec<crate::square::Square> = crate::move_generator::get_pseudo_legal_moves(board, position.get_side_to_move())
+        .into_iter()
+        .filter(|other| other.get_to() == chess_move.get_to() && other.get_from() != from)
+        .filter(|other| match get_piece_at_square(board, &other.get_from()) {
+            Some(piece) => piece.get_type() == piece_type,
+            None => false,
+        })
+        .map(|other| other.get_from())
+        .collect();
+    if others.is_empty() {
+        return String::new()
+    }
+    if others.iter().all(|other| get_nth_file(other) != get_nth_file(&from)) {
+        return from.get_file()
+    }
+    if others.iter().all(|other| get_nth_rank(other) != get_nth_rank(&from)) {
+        return from.get_rank().to_string()
+    }
+    from.get()
+}
+
+/// Get the regular expression of a SAN move that is not castling,
+/// which is compiled once
+fn get_san_regex() -> &'static regex::Regex {
+    static SAN_REGEX: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+    SAN_REGEX.get_or_init(|| regex::Regex::new(r"^([KQRBN])?([a-h])?([1-8])?x?([a-h][1-8])(=?([QRBN]))?$").unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::square::Square;
+
+    fn get_san_from_fen(fen: &str, from: &str, to: &str) -> String {
+        let position = crate::fen::position_from_fen(fen).unwrap();
+        get_san(&position, &Move::new(Square::new(from), Square::new(to)))
+    }
+
+    #[test]
+    fn pawn_capture() {
+        let fen = "rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2";
+        assert_eq!(get_san_from_fen(fen, "e4", "d5"), "exd5");
+    }
+    #[test]
+    fn en_passant_capture() {
+        let fen = "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3";
+        assert_eq!(get_san_from_fen(fen, "e5", "d6"), "exd6");
+    }
+    #[test]
+    fn castling() {
+        let fen = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+        assert_eq!(get_san_from_fen(fen, "e1", "g1"), "O-O");
+        assert_eq!(get_san_from_fen(fen, "e1", "c1"), "O-O-O");
+    }
+    #[test]
+    fn promotion() {
+        let position = crate::fen::position_from_fen("1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let mut chess_move = Move::new(Square::new("a7"), Square::new("b8"));
+        chess_move.set_promotion(Some(PieceType::Knight));
+        assert_eq!(get_san(&position, &chess_move), "axb8=N");
+    }
+    #[test]
+    fn disambiguate_by_file() {
+        let fen = "4k3/8/8/8/8/8/8/1N2KN2 w - - 0 1";
+        assert_eq!(get_san_from_fen(fen, "b1", "d2"), "Nbd2");
+    }
+    #[test]
+    fn disambiguate_by_rank() {
+        let fen = "4k3/8/8/R7/8/8/8/R3K3 w - - 0 1";
+        assert_eq!(get_san_from_fen(fen, "a1", "a3"), "R1a3");
+    }
+    #[test]
+    fn disambiguate_by_square() {
+        let fen = "4k3/8/8/8/8/2Q1Q3/8/4Q1K1 w - - 0 1";
+        assert_eq!(get_san_from_fen(fen, "e3", "d2"), "Qe3d2");
+    }
+    #[test]
+    fn san_round_trip() {
+        let rules = crate::rules::Rules::new();
+        for fen in [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "4k3/8/8/8/8/2Q1Q3/8/4Q1K1 w - - 0 1",
+            "1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1",
+        ] {
+            let position = crate::fen::position_from_fen(fen).unwrap();
+            for chess_move in position.get_pseudo_legal_moves(&rules) {
+                let san = get_san(&position, &chess_move);
+                let parsed = get_move_from_san(&position, &rules, &san).unwrap();
+                assert_eq!(parsed.get_from(), chess_move.get_from(), "{}", san);
+                assert_eq!(parsed.get_to(), chess_move.get_to(), "{}", san);
+                assert_eq!(parsed.get_promotion(), chess_move.get_promotion(), "{}", san);
+            }
+        }
+    }
+    #[test]
+    fn parse_castling_and_annotations() {
+        let position = crate::fen::position_from_fen("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1").unwrap();
+        let rules = crate::rules::Rules::new();
+        assert_eq!(get_move_from_san(&position, &rules, "0-0-0!?").unwrap(), Move::new(Square::new("e8"), Square::new("c8")));
+        assert_eq!(get_move_from_san(&position, &rules, "O-O+").unwrap(), Move::new(Square::new("e8"), Square::new("g8")));
+    }
+    #[test]
+    fn parse_ambiguous_move() {
+        let position = crate::fen::position_from_fen("4k3/8/8/8/8/8/8/1N2KN2 w - - 0 1").unwrap();
+        let rules = crate::rules::Rules::new();
+        assert_eq!(get_move_from_san(&position, &rules, "Nd2"), Err(SanError::Ambiguous("Nd2".to_string())));
+    }
+    #[test]
+    fn parse_promotion_without_piece() {
+        let position = crate::fen::position_from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let rules = crate::rules::Rules::new();
+        assert_eq!(get_move_from_san(&position, &rules, "a8"), Err(SanError::NoSuchMove("a8".to_string())));
+        assert_eq!(get_move_from_san(&position, &rules, "a8Q").unwrap().get_promotion(), Some(PieceType::Queen));
+    }
+}
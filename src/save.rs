@@ -0,0 +1,89 @@
+use crate::game::Game;
+use crate::headless::CommandError;
+
+/// The first line of a saved game
+const SAVE_HEADER: &str = "# Search And Destroy Chess 2";
+
+/// Write a game in our save format, which is a list of commands
+/// of the headless game loop: the position the game started from,
+/// if it is not the start position, followed by one move per line
+/// in coordinate notation. The moves that were undone are not saved.
+/// A saved game can be continued with `headless`
+///
+/// ```
+/// use search_and_destroy_chess_2::chess_move::Move;
+/// use search_and_destroy_chess_2::game::Game;
+/// use search_and_destroy_chess_2::save::to_save;
+/// use search_and_destroy_chess_2::square::Square;
+///
+/// let mut game = Game::new();
+/// game.do_move(&Move::new(Square::new("e2"), Square::new("e4")));
+/// assert_eq!(to_save(&game), "# Search And Destroy Chess 2\ne2e4\n");
+/// ```
+pub fn to_save(game: &Game) -> String {
+    let first_position = match game.get_history().first() {
+        Some(entry) => entry.get_position(),
+        None => game.get_position(),
+    };
+    let mut text = format!("{}\n", SAVE_HEADER);
+    if *first_position != crate::position::Position::new() {
+        text.push_str(&format!("position {}\n", crate::fen::position_to_fen(first_position)));
+    }
+    for entry in game.get_history() {
+        text.push_str(&crate::chess_move::to_str(entry.get_move()));
+        text.push('\n');
+    }
+    text
+}
+
+/// Read a game in our save format.
+/// As a saved game is a list of commands of the headless game loop,
+/// any command is allowed, yet the first command that cannot be done is an error
+///
+/// ```
+/// use search_and_destroy_chess_2::color::Color;
+/// use search_and_destroy_chess_2::save::game_from_save;
+///
+/// let game = game_from_save("position 4k3/8/8/8/8/8/8/4K2R w K - 0 1\ne1g1\n").unwrap();
+/// assert_eq!(game.get_history().len(), 1);
+/// assert_eq!(game.get_current_player(), Color::Black);
+/// assert!(game_from_save("e2e5\n").is_err());
+/// ```
+pub fn game_from_save(text: &str) -> Result<Game, CommandError> {
+    let mut game = Game::new();
+    let errors = crate::headless::run_headless(&mut game, text.as_bytes(), &mut std::io::sink())
+        .expect("Reading from memory and writing to a sink cannot fail");
+    match errors.into_iter().next() {
+        Some(error) => Err(error),
+        None => Ok(game),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let game = crate::pgn::game_from_pgn("1. e4 f5 2. Qh5 g6 3. Qxg6 h6 4. Qxe8 1-0").unwrap();
+        let loaded = game_from_save(&to_save(&game)).unwrap();
+        assert_eq!(loaded.get_history(), game.get_history());
+        assert_eq!(loaded.get_status(), crate::game_status::GameStatus::WhiteWins);
+    }
+    #[test]
+    fn round_trip_from_position() {
+        let mut game = Game::new();
+        game.set_position(crate::fen::position_from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap());
+        let chess_move = crate::headless::get_move_from_text(game.get_position(), &game.get_rules(), "a8=R").unwrap();
+        game.do_move(&chess_move);
+        let text = to_save(&game);
+        assert_eq!(text, "# Search And Destroy Chess 2\nposition 4k3/P7/8/8/8/8/8/4K3 w - - 0 1\na7a8r\n");
+        assert_eq!(game_from_save(&text).unwrap().get_position(), game.get_position());
+    }
+    #[test]
+    fn undone_moves_are_not_saved() {
+        let mut game = game_from_save("e2e4\ne7e5\n").unwrap();
+        game.undo();
+        assert_eq!(to_save(&game), "# Search And Destroy Chess 2\ne2e4\n");
+    }
+}
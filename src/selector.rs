@@ -41,7 +41,7 @@ impl Selector {
     /// selector.set_cursor(None);
     /// assert_eq!(selector.get_cursor(), None);
     /// ```
-    pub fn get_cursor(&self) -> Option<crate::square::Square> { self.cursor.clone() }
+    pub fn get_cursor(&self) -> Option<crate::square::Square> { self.cursor }
 
     /// Get the selected 'from' square
     /// 
@@ -57,7 +57,7 @@ impl Selector {
     /// selector.set_from(None);
     /// assert_eq!(selector.get_from(), None);
     /// ```
-    pub fn get_from(&self) -> Option<crate::square::Square> { self.from.clone() }
+    pub fn get_from(&self) -> Option<crate::square::Square> { self.from }
 
     /// Get the selected 'to' square
     /// 
@@ -73,13 +73,13 @@ impl Selector {
     /// selector.set_to(None);
     /// assert_eq!(selector.get_to(), None);
     /// ```
-    pub fn get_to(&self) -> Option<crate::square::Square> { self.to.clone() }
+    pub fn get_to(&self) -> Option<crate::square::Square> { self.to }
 
     /// Moves the cursor up, if there is one.
     /// If the cursor leaves the top of the screen, the cursor will be put at the bottom
     pub fn move_cursor(&mut self, direction: crate::direction::Direction) {
         if let Some(some_cursor) = &self.cursor {
-            self.cursor = Some(crate::square::get_square_at(some_cursor.clone(), direction));
+            self.cursor = Some(crate::square::get_square_at(*some_cursor, direction));
         }
     }
 
@@ -87,7 +87,7 @@ impl Selector {
     /// If the cursor leaves the top of the screen, the cursor will be put at the bottom
     pub fn move_cursor_up(&mut self) {
         if let Some(some_cursor) = &self.cursor {
-            self.cursor = Some(crate::square::get_square_above(some_cursor.clone()));
+            self.cursor = Some(crate::square::get_square_above(*some_cursor));
         }
     }
 
@@ -140,6 +140,12 @@ impl Selector {
     pub fn set_to(&mut self, to: Option<crate::square::Square>) { self.to = to }
 }
 
+impl Default for Selector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -156,7 +162,7 @@ mod tests {
         let mut selector = Selector::new();
         assert_eq!(selector.get_cursor(), None);
         let random_square = crate::square::get_random_square();
-        selector.set_cursor(Some(random_square.clone()));
+        selector.set_cursor(Some(random_square));
         assert_eq!(selector.get_cursor(), Some(random_square));
         selector.set_cursor(None);
         assert_eq!(selector.get_cursor(), None);
@@ -166,7 +172,7 @@ mod tests {
         let mut selector = Selector::new();
         assert_eq!(selector.get_from(), None);
         let random_square = crate::square::get_random_square();
-        selector.set_from(Some(random_square.clone()));
+        selector.set_from(Some(random_square));
         assert_eq!(selector.get_from(), Some(random_square));
         selector.set_from(None);
         assert_eq!(selector.get_from(), None);
@@ -176,7 +182,7 @@ mod tests {
         let mut selector = Selector::new();
         assert_eq!(selector.get_to(), None);
         let random_square = crate::square::get_random_square();
-        selector.set_to(Some(random_square.clone()));
+        selector.set_to(Some(random_square));
         assert_eq!(selector.get_to(), Some(random_square));
         selector.set_to(None);
         assert_eq!(selector.get_to(), None);
@@ -185,7 +191,7 @@ mod tests {
     fn move_cursor() {
         let mut selector = Selector::new();
         let random_square = crate::square::get_random_square();
-        selector.set_cursor(Some(random_square.clone()));
+        selector.set_cursor(Some(random_square));
         let before = selector.get_cursor();
         selector.move_cursor_up();
         let after = selector.get_cursor();
@@ -1,50 +1,56 @@
 use crate::file_index::FileIndex;
 
-/// A square on a chess board
-#[derive(Debug, PartialEq, Eq, Clone)]
+/// A square on a chess board.
+/// Stored as an index from 0 to 63, starting at a1, then b1, up to h8,
+/// so that a square is cheap to copy
+#[derive(PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
 pub struct Square {
-    coordinat: String,
+    index: u8,
 }
 
 impl Square {
+    /// Create a new square from a string, e.g. 'a1'.
+    /// Panics if the string is not a coordinat,
+    /// use `str::parse` or `Square::try_from` for strings from a user
     /// ```
     /// use search_and_destroy_chess_2::square::Square;
     /// 
-    /// let square = Square::new("c5");
-    /// let square_again = square.clone();
+    /// let square = Square::new("b3");
+    /// let square_again = Square::new("b3");
     /// assert_eq!(square, square_again);
     /// ```
-    pub fn clone(&self) -> Square {
-        Square {
-            coordinat: String::from(self.coordinat.clone()),
+    pub fn new(coordinat_str: &str) -> Square {
+        match coordinat_str.parse() {
+            Ok(square) => square,
+            Err(error) => panic!("{}", error),
         }
     }
-    /// Create a new square from a string, e.g. 'a1'
+    /// Create a new square from its index, from 0 for 'a1' to 63 for 'h8'
     /// ```
     /// use search_and_destroy_chess_2::square::Square;
     /// 
-    /// let square = Square::new("b3");
-    /// let square_again = Square::new("b3");
-    /// assert_eq!(square, square_again);
+    /// assert_eq!(Square::from_index(0), Square::new("a1"));
+    /// assert_eq!(Square::from_index(9), Square::new("b2"));
+    /// assert_eq!(Square::from_index(63), Square::new("h8"));
     /// ```
-    pub fn new(coordinat_str: &str) -> Square {
-        if coordinat_str.len() != 2 {
-            panic!("A coordinat has two characters");
-        }
-        let file_regex = regex::Regex::new(r"^[a-h].$").unwrap();
-        if !file_regex.is_match(coordinat_str) {
-            panic!("The file must be 'a..h'");
-        }
-        let rank_regex = regex::Regex::new(r"^.[1-8]$").unwrap();
-        if !rank_regex.is_match(coordinat_str) {
-            panic!("The rank must be '1..8'");
-        }
+    pub fn from_index(index: u8) -> Square {
+        assert!(index < 64, "Use a square index from [0..64]");
         Square {
-            coordinat: String::from(coordinat_str),
+            index,
         }
     }
+    /// Create a new square from its file and rank indices, e.g. (0, 0) for 'a1'
+    /// ```
+    /// use search_and_destroy_chess_2::square::Square;
+    /// 
+    /// assert_eq!(Square::from_indices(4, 1), Square::new("e2"));
+    /// ```
+    pub fn from_indices(file_index: usize, rank_index: usize) -> Square {
+        assert!(file_index < 8, "Use a file_index from [0..8]");
+        assert!(rank_index < 8, "Use a rank_index from [0..8]");
+        Square::from_index((rank_index * 8 + file_index) as u8)
+    }
     /// Get the coordinat of the Square, e.g. 'a1'
-    /// Create a new square from a string, e.g. 'a1'
     /// ```
     /// use search_and_destroy_chess_2::square::Square;
     /// 
@@ -52,7 +58,7 @@ impl Square {
     /// assert_eq!(square.get(), "b3");
     /// ```
     pub fn get(&self) -> String {
-        self.coordinat.clone()
+        format!("{}{}", self.get_file(), self.get_rank())
     }
     /// Get the file of the Square, e.g. 'a'
     /// ```
@@ -62,8 +68,25 @@ impl Square {
     /// assert_eq!(square.get_file(), "e");
     /// ```
     pub fn get_file(&self) -> String {
-        let copy: String = self.coordinat.clone();
-        copy.chars().next().unwrap().to_string()
+        ((b'a' + self.get_file_index() as u8) as char).to_string()
+    }
+    /// Get the index of the file of the Square, from 0 for 'a' to 7 for 'h'
+    /// ```
+    /// use search_and_destroy_chess_2::square::Square;
+    /// 
+    /// assert_eq!(Square::new("c7").get_file_index(), 2);
+    /// ```
+    pub fn get_file_index(&self) -> usize {
+        (self.index % 8) as usize
+    }
+    /// Get the index of the Square, from 0 for 'a1' to 63 for 'h8'
+    /// ```
+    /// use search_and_destroy_chess_2::square::Square;
+    /// 
+    /// assert_eq!(Square::new("h1").get_index(), 7);
+    /// ```
+    pub fn get_index(&self) -> u8 {
+        self.index
     }
     /// Get the rank of the Square, e.g. '1'
     /// ```
@@ -73,14 +96,110 @@ impl Square {
     /// assert_eq!(square.get_rank(), 7);
     /// ```
     pub fn get_rank(&self) -> usize {
-        let copy: String = self.coordinat.clone();
-        let slice = &copy[1..2];
-        let first_str = String::from(slice);
-        let rank: usize = first_str.parse().unwrap();
-        rank
+        self.get_rank_index() + 1
+    }
+    /// Get the index of the rank of the Square, from 0 for '1' to 7 for '8'
+    /// ```
+    /// use search_and_destroy_chess_2::square::Square;
+    /// 
+    /// assert_eq!(Square::new("c7").get_rank_index(), 6);
+    /// ```
+    pub fn get_rank_index(&self) -> usize {
+        (self.index / 8) as usize
+    }
+}
+
+impl std::fmt::Debug for Square {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Square({})", self.get())
+    }
+}
+
+impl std::fmt::Display for Square {
+    /// Show the coordinat of the Square, e.g. 'a1'
+    /// ```
+    /// use search_and_destroy_chess_2::square::Square;
+    /// 
+    /// assert_eq!(format!("{}", Square::new("g5")), "g5");
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.get())
+    }
+}
+
+impl std::str::FromStr for Square {
+    type Err = SquareError;
+
+    /// Create a new square from a string, e.g. 'a1',
+    /// or tell why the string is not a coordinat
+    /// ```
+    /// use search_and_destroy_chess_2::square::Square;
+    /// use search_and_destroy_chess_2::square::SquareError;
+    /// 
+    /// assert_eq!("b3".parse::<Square>(), Ok(Square::new("b3")));
+    /// assert_eq!("b9".parse::<Square>(), Err(SquareError::InvalidRank('9')));
+    /// ```
+    fn from_str(coordinat_str: &str) -> Result<Square, SquareError> {
+        let chars: Vec<char> = coordinat_str.chars().collect();
+        if chars.len() != 2 {
+            return Err(SquareError::WrongLength(coordinat_str.to_string()));
+        }
+        if !('a'..='h').contains(&chars[0]) {
+            return Err(SquareError::InvalidFile(chars[0]));
+        }
+        if !('1'..='8').contains(&chars[1]) {
+            return Err(SquareError::InvalidRank(chars[1]));
+        }
+        let file_index = chars[0] as usize - 'a' as usize;
+        let rank_index = chars[1] as usize - '1' as usize;
+        Ok(Square::from_indices(file_index, rank_index))
+    }
+}
+
+impl<'a> std::convert::TryFrom<&'a str> for Square {
+    type Error = SquareError;
+
+    /// Create a new square from a string, e.g. 'a1',
+    /// or tell why the string is not a coordinat
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use search_and_destroy_chess_2::square::Square;
+    /// use search_and_destroy_chess_2::square::SquareError;
+    /// 
+    /// assert_eq!(Square::try_from("h8"), Ok(Square::new("h8")));
+    /// assert_eq!(Square::try_from("i8"), Err(SquareError::InvalidFile('i')));
+    /// ```
+    fn try_from(coordinat_str: &'a str) -> Result<Square, SquareError> {
+        coordinat_str.parse()
+    }
+}
+
+/// The reasons why a string is not the coordinat of a square
+/// ```
+/// use search_and_destroy_chess_2::square::SquareError;
+/// 
+/// let error = SquareError::WrongLength("a10".to_string());
+/// assert_eq!(error.to_string(), "A coordinat has two characters, found 'a10'");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SquareError {
+    WrongLength(String),
+    InvalidFile(char),
+    InvalidRank(char),
+}
+
+impl std::fmt::Display for SquareError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SquareError::WrongLength(coordinat) => write!(f, "A coordinat has two characters, found '{}'", coordinat),
+            SquareError::InvalidFile(file) => write!(f, "The file must be 'a..h', found '{}'", file),
+            SquareError::InvalidRank(rank) => write!(f, "The rank must be '1..8', found '{}'", rank),
+        }
     }
 }
 
+impl std::error::Error for SquareError {}
+
 /// Create a coordinat string from two indices
 /// ```
 /// use search_and_destroy_chess_2::file_index::FileIndex;
@@ -92,30 +211,7 @@ impl Square {
 /// assert_eq!(create_coordinat_from_indices(&FileIndex::new(7), 7), String::from("h8"));
 /// ```
 pub fn create_coordinat_from_indices(file_index: &FileIndex, rank_index: u8) -> String {
-    let file_str = match file_index.get() {
-        0 => String::from("a"),
-        1 => String::from("b"),
-        2 => String::from("c"),
-        3 => String::from("d"),
-        4 => String::from("e"),
-        5 => String::from("f"),
-        6 => String::from("g"),
-        7 => String::from("h"),
-        _ => panic!("Use a file_index from [0..8]")
-    };
-    let rank_str = match rank_index {
-        0 => String::from("1"),
-        1 => String::from("2"),
-        2 => String::from("3"),
-        3 => String::from("4"),
-        4 => String::from("5"),
-        5 => String::from("6"),
-        6 => String::from("7"),
-        7 => String::from("8"),
-        _ => panic!("Use a rank_index from [0..8]")
-    };
-    let str = file_str + &rank_str;
-    str
+    Square::from_indices(file_index.get(), rank_index as usize).get()
 }
 
 /// Get all the 64 coordinats on a chessboard
@@ -127,28 +223,25 @@ pub fn create_coordinat_from_indices(file_index: &FileIndex, rank_index: u8) ->
 /// assert_eq!(coordinats.len(), 64);
 /// ```
 pub fn get_all_coordinats() -> Vec<String> {
-    let mut squares: Vec<String> = Vec::new();
-    for file_index in crate::file_index::get_all_file_indices() {
-        for rank_index in 0..8 {
-            squares.push(create_coordinat_from_indices(&file_index, rank_index));
-        }
-    }
-    squares
+    get_all_squares().iter().map(|square| square.get()).collect()
 }
 
-/// Get all the 64 squares on a chessboard
+/// Get all the 64 squares on a chessboard, file by file: from 'a1', 'a2' up to 'h8'
 /// ```
 /// use search_and_destroy_chess_2::square::Square;
 /// use search_and_destroy_chess_2::square::get_all_squares;
 /// 
 /// let squares = get_all_squares();
 /// assert_eq!(squares.len(), 64);
+/// assert_eq!(squares[1], Square::new("a2"));
+/// assert_eq!(squares[8], Square::new("b1"));
 /// ```
 pub fn get_all_squares() -> Vec<Square> {
-    let coordinats = crate::square::get_all_coordinats();
     let mut squares: Vec<Square> = Vec::new();
-    for coordinat in coordinats {
-        squares.push(Square::new(&coordinat));
+    for file_index in 0..8 {
+        for rank_index in 0..8 {
+            squares.push(Square::from_indices(file_index, rank_index));
+        }
     }
     squares
 }
@@ -168,8 +261,7 @@ pub fn get_all_squares() -> Vec<Square> {
 /// assert_eq!(get_nth_file(&square).get(), 7);
 /// ```
 pub fn get_nth_file(square: &Square) -> FileIndex {
-    let char_vec: Vec<char> = square.get_file().chars().collect();
-    crate::file_index::FileIndex::new((char_vec[0] as i32 - 'a' as i32) as usize)
+    crate::file_index::FileIndex::new(square.get_file_index())
 }
 
 /// Get the nth rank of the Square, e.g. '0' for the first rank
@@ -184,7 +276,7 @@ pub fn get_nth_file(square: &Square) -> FileIndex {
 /// assert_eq!(get_nth_rank(&square), 7);
 /// ```
 pub fn get_nth_rank(square: &Square) -> usize {
-    square.get_rank() - 1_usize
+    square.get_rank_index()
 }
 
 /// Get a random chess board coordinat
@@ -209,7 +301,10 @@ pub fn get_random_coordinat() -> String {
 /// let square = get_random_square();
 /// ```
 pub fn get_random_square() -> crate::square::Square {
-    crate::square::Square::new(&get_random_coordinat())
+    crate::square::Square::from_indices(
+        crate::file_index::create_random_file_index().get(),
+        crate::rank_index::create_random_rank_index()
+    )
 }
 
 /// Get the square above a square on a chessboard
@@ -224,12 +319,7 @@ pub fn get_random_square() -> crate::square::Square {
 /// assert_eq!(square_3.get(), String::from("d1"));
 /// ```
 pub fn get_square_above(square: Square) -> crate::square::Square {
-    crate::square::Square::new(
-        &crate::square::create_coordinat_from_indices(
-            &crate::square::get_nth_file(&square),
-            ((crate::square::get_nth_rank(&square) + 1) % 8) as u8
-        )
-    )
+    Square::from_indices(square.get_file_index(), (square.get_rank_index() + 1) % 8)
 }
 
 /// Get the square at a relative direction
@@ -265,12 +355,7 @@ pub fn get_square_at(square: Square, direction: crate::direction::Direction) ->
 /// assert_eq!(square_3.get(), String::from("h7"));
 /// ```
 pub fn get_square_at_lhs(square: Square) -> crate::square::Square {
-    crate::square::Square::new(
-        &crate::square::create_coordinat_from_indices(
-            &crate::file_index::FileIndex::new((crate::square::get_nth_file(&square).get() + 7) % 8),
-            crate::square::get_nth_rank(&square) as u8
-        )
-    )
+    Square::from_indices((square.get_file_index() + 7) % 8, square.get_rank_index())
 }
 
 /// Get the square at the right hand side of another square on a chessboard
@@ -285,12 +370,26 @@ pub fn get_square_at_lhs(square: Square) -> crate::square::Square {
 /// assert_eq!(square_3.get(), String::from("a3"));
 /// ```
 pub fn get_square_at_rhs(square: Square) -> crate::square::Square {
-    crate::square::Square::new(
-        &crate::square::create_coordinat_from_indices(
-            &crate::file_index::FileIndex::new((crate::square::get_nth_file(&square).get() + 1) % 8),
-            crate::square::get_nth_rank(&square) as u8
-        )
-    )
+    Square::from_indices((square.get_file_index() + 1) % 8, square.get_rank_index())
+}
+
+/// Get the square at a relative offset, if that square is on the board.
+/// Unlike `get_square_at`, this does not wrap around the edges of the board.
+/// ```
+/// use search_and_destroy_chess_2::square::Square;
+/// use search_and_destroy_chess_2::square::get_square_at_offset;
+/// 
+/// let square = Square::new("b1");
+/// assert_eq!(get_square_at_offset(&square, 1, 2), Some(Square::new("c3")));
+/// assert_eq!(get_square_at_offset(&square, -2, 1), None);
+/// ```
+pub fn get_square_at_offset(square: &Square, file_offset: i32, rank_offset: i32) -> Option<crate::square::Square> {
+    let file_index = square.get_file_index() as i32 + file_offset;
+    let rank_index = square.get_rank_index() as i32 + rank_offset;
+    if !(0..8).contains(&file_index) || !(0..8).contains(&rank_index) {
+        return None
+    }
+    Some(Square::from_indices(file_index as usize, rank_index as usize))
 }
 
 /// Get the square below a square on a chessboard
@@ -305,12 +404,7 @@ pub fn get_square_at_rhs(square: Square) -> crate::square::Square {
 /// assert_eq!(square_3.get(), String::from("f8"));
 /// ```
 pub fn get_square_below(square: Square) -> crate::square::Square {
-    crate::square::Square::new(
-        &crate::square::create_coordinat_from_indices(
-            &crate::square::get_nth_file(&square),
-            ((crate::square::get_nth_rank(&square) + 7) % 8) as u8
-        )
-    )
+    Square::from_indices(square.get_file_index(), (square.get_rank_index() + 7) % 8)
 }
 
 
@@ -324,7 +418,21 @@ mod tests {
     fn create_square() {
         let str = "a2";
         let square = Square::new(str);
-        assert_eq!(str, square.coordinat);
+        assert_eq!(square.get_index(), 8);
+    }
+    #[test]
+    fn index_round_trip() {
+        for index in 0..64 {
+            let square = Square::from_index(index);
+            assert_eq!(square.get_index(), index);
+            assert_eq!(Square::new(&square.get()), square);
+            assert_eq!(Square::from_indices(square.get_file_index(), square.get_rank_index()), square);
+        }
+    }
+    #[test]
+    #[should_panic(expected = "Use a square index from [0..64]")]
+    fn invalid_index() {
+        Square::from_index(64);
     }
 
     #[test]
@@ -380,6 +488,19 @@ mod tests {
         Square::new("a9");
     }
     #[test]
+    fn parse_invalid_squares() {
+        assert_eq!("".parse::<Square>(), Err(SquareError::WrongLength("".to_string())));
+        assert_eq!("é1".parse::<Square>(), Err(SquareError::InvalidFile('é')));
+        assert_eq!("A1".parse::<Square>(), Err(SquareError::InvalidFile('A')));
+        assert_eq!("a0".parse::<Square>(), Err(SquareError::InvalidRank('0')));
+    }
+    #[test]
+    fn parse_all_coordinats() {
+        for coordinat in get_all_coordinats() {
+            assert_eq!(coordinat.parse::<Square>().unwrap().get(), coordinat);
+        }
+    }
+    #[test]
     fn get_all_squares() {
         assert_eq!(super::get_all_squares().len(), 64);
     }
@@ -408,6 +529,14 @@ mod tests {
         assert_eq!(square_3.get(), String::from("a3"));
     }
     #[test]
+    fn test_get_square_at_offset() {
+        assert_eq!(get_square_at_offset(&Square::new("d4"), 0, 0), Some(Square::new("d4")));
+        assert_eq!(get_square_at_offset(&Square::new("d4"), -3, 4), Some(Square::new("a8")));
+        assert_eq!(get_square_at_offset(&Square::new("h8"), 1, 0), None);
+        assert_eq!(get_square_at_offset(&Square::new("h8"), 0, 1), None);
+        assert_eq!(get_square_at_offset(&Square::new("a1"), 0, -1), None);
+    }
+    #[test]
     fn test_get_square_below() {
         let square_1 = Square::new("f2");
         let square_2 = get_square_below(square_1);